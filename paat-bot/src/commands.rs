@@ -0,0 +1,124 @@
+use anyhow::{anyhow, Result};
+use chrono::NaiveDate;
+use paat_core::{
+    datetime::{get_naive_date, get_naive_date_from_output_format},
+    types::Direction,
+};
+
+pub const HELP_TEXT: &str = "Commands:
+/watch <line> <date> <time> - wait for a spot, e.g. /watch VK 2026-07-03 17:30
+/list - list your watches
+/cancel <id> - stop a watch
+Lines: HR, RH, KV, VK";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Help,
+    Watch {
+        direction: Direction,
+        departure_date: NaiveDate,
+        departure_time: String,
+    },
+    List,
+    Cancel(u64),
+}
+
+impl Command {
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut words = text.split_whitespace();
+        let command = words.next().unwrap_or_default();
+        let command = command.split('@').next().unwrap_or_default();
+        match command {
+            "/start" | "/help" => Ok(Self::Help),
+            "/list" => Ok(Self::List),
+            "/cancel" => {
+                let id = words
+                    .next()
+                    .ok_or_else(|| anyhow!("Usage: /cancel <id>"))?
+                    .parse::<u64>()
+                    .map_err(|_| anyhow!("Watch id must be a number"))?;
+                Ok(Self::Cancel(id))
+            }
+            "/watch" => {
+                let (line, date, time) = match (words.next(), words.next(), words.next()) {
+                    (Some(line), Some(date), Some(time)) => (line, date, time),
+                    _ => return Err(anyhow!("Usage: /watch <line> <date> <time>")),
+                };
+                let direction = Direction::from_abbreviation(line)
+                    .ok_or_else(|| anyhow!("Unknown line {}, use HR, RH, KV or VK", line))?;
+                let departure_date = get_naive_date_from_output_format(date)
+                    .or_else(|_| get_naive_date(date))
+                    .map_err(|_| anyhow!("Unsupported date {}, use YYYY-MM-DD", date))?;
+                Ok(Self::Watch {
+                    direction,
+                    departure_date,
+                    departure_time: time.to_string(),
+                })
+            }
+            _ => Err(anyhow!("Unknown command\n\n{}", HELP_TEXT)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(text: &str) -> String {
+        Command::parse(text).unwrap_err().to_string()
+    }
+
+    fn watch_vk(date: &str) -> Result<Command> {
+        Command::parse(&format!("/watch vk {} 17:30", date))
+    }
+
+    #[test]
+    fn parses_commands_with_bot_name() {
+        assert_eq!(Command::parse("/start@paat_bot").unwrap(), Command::Help);
+        assert_eq!(Command::parse("/help").unwrap(), Command::Help);
+        assert_eq!(Command::parse("/list@paat_bot").unwrap(), Command::List);
+        assert_eq!(
+            Command::parse("/cancel@paat_bot 7").unwrap(),
+            Command::Cancel(7)
+        );
+    }
+
+    #[test]
+    fn parses_watch_in_both_date_formats() {
+        let expected = Command::Watch {
+            direction: Direction::from_abbreviation("VK").unwrap(),
+            departure_date: NaiveDate::from_ymd_opt(2026, 7, 3).unwrap(),
+            departure_time: "17:30".to_string(),
+        };
+        assert_eq!(watch_vk("2026-07-03").unwrap(), expected);
+        assert_eq!(watch_vk("03.07.2026").unwrap(), expected);
+    }
+
+    #[test]
+    fn rejects_bad_watch_arguments() {
+        assert_eq!(
+            parse_error("/watch XX 2026-07-03 17:30"),
+            "Unknown line XX, use HR, RH, KV or VK"
+        );
+        assert_eq!(
+            parse_error("/watch VK 07/03/2026 17:30"),
+            "Unsupported date 07/03/2026, use YYYY-MM-DD"
+        );
+        assert_eq!(
+            parse_error("/watch VK 2026-07-03"),
+            "Usage: /watch <line> <date> <time>"
+        );
+    }
+
+    #[test]
+    fn rejects_bad_cancel_arguments() {
+        assert_eq!(parse_error("/cancel"), "Usage: /cancel <id>");
+        assert_eq!(parse_error("/cancel first"), "Watch id must be a number");
+    }
+
+    #[test]
+    fn rejects_unknown_commands() {
+        assert!(parse_error("/book VK").starts_with("Unknown command"));
+        assert!(parse_error("").starts_with("Unknown command"));
+    }
+}
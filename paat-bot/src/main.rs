@@ -0,0 +1,85 @@
+mod commands;
+mod telegram;
+mod watches;
+
+use anyhow::{anyhow, Result};
+use commands::{Command, HELP_TEXT};
+use env_logger::init;
+use log::{error, warn};
+use paat_core::{client::Client, constants::TIMEOUT_BETWEEN_REQUESTS};
+use std::time::Duration;
+use telegram::{TelegramClient, Update, DEFAULT_API_URL};
+use tokio::{signal::ctrl_c, time::sleep};
+use watches::Watches;
+
+async fn handle_command(watches: &Watches, chat_id: i64, text: &str) -> Result<String> {
+    match Command::parse(text)? {
+        Command::Help => Ok(HELP_TEXT.to_string()),
+        Command::Watch {
+            direction,
+            departure_date,
+            departure_time,
+        } => {
+            watches
+                .add(chat_id, direction, departure_date, &departure_time)
+                .await
+        }
+        Command::List => Ok(watches.list(chat_id).await),
+        Command::Cancel(id) => watches.cancel(chat_id, id).await,
+    }
+}
+
+async fn handle_update(telegram: &TelegramClient, watches: &Watches, update: Update) {
+    if let Some(message) = update.message {
+        if let Some(text) = message.text {
+            let reply = handle_command(watches, message.chat.id, &text)
+                .await
+                .unwrap_or_else(|err| err.to_string());
+            if let Err(err) = telegram.send_message(message.chat.id, &reply).await {
+                error!("Failed to reply to chat {}: {}", message.chat.id, err);
+            }
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let timeout_between_requests = std::env::var("TIMEOUT_BETWEEN_REQUESTS")
+        .map(|timeout| timeout.parse::<u64>().unwrap_or(TIMEOUT_BETWEEN_REQUESTS))
+        .unwrap_or(TIMEOUT_BETWEEN_REQUESTS);
+    init();
+    let token = std::env::var("TELEGRAM_BOT_TOKEN")
+        .map_err(|_| anyhow!("TELEGRAM_BOT_TOKEN environment variable is not set"))?;
+    let api_url = std::env::var("TELEGRAM_API_URL").unwrap_or_else(|_| DEFAULT_API_URL.to_string());
+
+    let telegram = TelegramClient::new(&api_url, &token);
+    let client = Client::new(Duration::from_secs(timeout_between_requests));
+    let watches = Watches::new(client, telegram.clone());
+
+    let mut offset = 0;
+    loop {
+        tokio::select! {
+            _ = ctrl_c() => {
+                println!("Goodbye!");
+                return Ok(());
+            }
+            updates = telegram.get_updates(offset) => match updates {
+                Ok(updates) => {
+                    for update in updates {
+                        offset = offset.max(update.update_id + 1);
+                        // A slow sailing lookup must not hold up other chats
+                        let telegram = telegram.clone();
+                        let watches = watches.clone();
+                        tokio::spawn(async move {
+                            handle_update(&telegram, &watches, update).await;
+                        });
+                    }
+                }
+                Err(err) => {
+                    warn!("Failed to fetch updates: {}", err);
+                    sleep(Duration::from_secs(timeout_between_requests)).await;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use anyhow::{anyhow, Result};
+use reqwest::Client as ReqwestClient;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+pub const DEFAULT_API_URL: &str = "https://api.telegram.org";
+const LONG_POLL_TIMEOUT_SECONDS: u64 = 30;
+
+#[derive(Deserialize, Debug)]
+struct ApiResponse<T> {
+    ok: bool,
+    result: Option<T>,
+    description: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Update {
+    pub update_id: i64,
+    pub message: Option<Message>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Message {
+    pub chat: Chat,
+    pub text: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Chat {
+    pub id: i64,
+}
+
+#[derive(Serialize)]
+struct GetUpdates {
+    offset: i64,
+    timeout: u64,
+}
+
+#[derive(Serialize)]
+struct SendMessage<'a> {
+    chat_id: i64,
+    text: &'a str,
+}
+
+#[derive(Clone)]
+pub struct TelegramClient {
+    client: ReqwestClient,
+    base_url: String,
+}
+
+impl TelegramClient {
+    pub fn new(api_url: &str, token: &str) -> Self {
+        Self {
+            client: ReqwestClient::new(),
+            base_url: format!("{}/bot{}", api_url.trim_end_matches('/'), token),
+        }
+    }
+
+    async fn call<P, T>(&self, method: &str, payload: &P) -> Result<T>
+    where
+        P: Serialize,
+        T: DeserializeOwned,
+    {
+        let response = self
+            .client
+            .post(format!("{}/{}", self.base_url, method))
+            .json(payload)
+            .send()
+            .await?
+            .json::<ApiResponse<T>>()
+            .await?;
+        match response {
+            ApiResponse {
+                ok: true,
+                result: Some(result),
+                ..
+            } => Ok(result),
+            ApiResponse { description, .. } => Err(anyhow!(
+                "Telegram method {} failed: {}",
+                method,
+                description.unwrap_or_default()
+            )),
+        }
+    }
+
+    pub async fn get_updates(&self, offset: i64) -> Result<Vec<Update>> {
+        self.call(
+            "getUpdates",
+            &GetUpdates {
+                offset,
+                timeout: LONG_POLL_TIMEOUT_SECONDS,
+            },
+        )
+        .await
+    }
+
+    pub async fn send_message(&self, chat_id: i64, text: &str) -> Result<()> {
+        self.call::<_, serde_json::Value>("sendMessage", &SendMessage { chat_id, text })
+            .await?;
+        Ok(())
+    }
+}
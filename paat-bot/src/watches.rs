@@ -0,0 +1,187 @@
+use crate::telegram::TelegramClient;
+use anyhow::{anyhow, Result};
+use chrono::NaiveDate;
+use futures::{lock::Mutex, StreamExt};
+use log::{error, warn};
+use paat_core::{
+    client::Client,
+    datetime::{naive_date_to_output_string, service_datetime_to_local_time_string},
+    notification::{Notification, NotificationKind},
+    types::{
+        event::{Event, WaitForSpot},
+        Direction,
+    },
+};
+use std::{collections::BTreeMap, sync::Arc};
+use tokio::task::JoinHandle;
+
+struct Watch {
+    chat_id: i64,
+    direction: Direction,
+    departure_date: NaiveDate,
+    event: Event,
+    handle: JoinHandle<()>,
+}
+
+#[derive(Default)]
+struct WatchList {
+    next_id: u64,
+    watches: BTreeMap<u64, Watch>,
+}
+
+#[derive(Clone)]
+pub struct Watches {
+    client: Client,
+    telegram: TelegramClient,
+    watch_list: Arc<Mutex<WatchList>>,
+}
+
+impl Watches {
+    pub fn new(client: Client, telegram: TelegramClient) -> Self {
+        Self {
+            client,
+            telegram,
+            watch_list: Arc::new(Mutex::new(WatchList::default())),
+        }
+    }
+
+    async fn find_event(
+        &self,
+        direction: &Direction,
+        departure_date: &NaiveDate,
+        departure_time: &str,
+    ) -> Result<Event> {
+        let event_map = self.client.fetch_events(departure_date, direction).await?;
+        event_map
+            .into_values()
+            .find(|event| {
+                service_datetime_to_local_time_string(&event.start).ok()
+                    == Some(departure_time.to_string())
+            })
+            .ok_or_else(|| {
+                anyhow!(
+                    "No ferry at {} on {} {}",
+                    departure_time,
+                    direction,
+                    naive_date_to_output_string(departure_date)
+                )
+            })
+    }
+
+    pub async fn add(
+        &self,
+        chat_id: i64,
+        direction: Direction,
+        departure_date: NaiveDate,
+        departure_time: &str,
+    ) -> Result<String> {
+        let event = self
+            .find_event(&direction, &departure_date, departure_time)
+            .await?;
+        let mut watch_list = self.watch_list.lock().await;
+        watch_list.next_id += 1;
+        let id = watch_list.next_id;
+        let handle = tokio::spawn(self.clone().wait_for_spot(
+            id,
+            chat_id,
+            direction,
+            departure_date,
+            event.uuid.clone(),
+        ));
+        let reply = format!(
+            "Watching #{}: {} {} {}",
+            id,
+            direction,
+            naive_date_to_output_string(&departure_date),
+            event
+        );
+        watch_list.watches.insert(
+            id,
+            Watch {
+                chat_id,
+                direction,
+                departure_date,
+                event,
+                handle,
+            },
+        );
+        Ok(reply)
+    }
+
+    pub async fn list(&self, chat_id: i64) -> String {
+        let watch_list = self.watch_list.lock().await;
+        let lines = watch_list
+            .watches
+            .iter()
+            .filter(|(_, watch)| watch.chat_id == chat_id)
+            .map(|(id, watch)| {
+                format!(
+                    "#{}: {} {} {}",
+                    id,
+                    watch.direction,
+                    naive_date_to_output_string(&watch.departure_date),
+                    watch.event
+                )
+            })
+            .collect::<Vec<String>>();
+        if lines.is_empty() {
+            return String::from("No active watches");
+        }
+        lines.join("\n")
+    }
+
+    pub async fn cancel(&self, chat_id: i64, id: u64) -> Result<String> {
+        let mut watch_list = self.watch_list.lock().await;
+        match watch_list.watches.get(&id) {
+            Some(watch) if watch.chat_id == chat_id => {
+                if let Some(watch) = watch_list.watches.remove(&id) {
+                    watch.handle.abort();
+                }
+                Ok(format!("Cancelled #{}", id))
+            }
+            _ => Err(anyhow!("No watch #{}", id)),
+        }
+    }
+
+    async fn wait_for_spot(
+        self,
+        id: u64,
+        chat_id: i64,
+        direction: Direction,
+        departure_date: NaiveDate,
+        event_uuid: String,
+    ) {
+        let mut wait_stream = Box::pin(self.client.create_wait_stream(
+            &departure_date,
+            &direction,
+            &event_uuid,
+        ));
+        while let Some(wait_result) = wait_stream.next().await {
+            match wait_result {
+                Ok(WaitForSpot::Done(event)) => {
+                    let notification = Notification::new(
+                        NotificationKind::SpotFound,
+                        event,
+                        direction,
+                        departure_date,
+                    );
+                    let text = format!("#{} {}", id, notification);
+                    if let Err(err) = self.telegram.send_message(chat_id, &text).await {
+                        error!("Failed to send alert for watch #{}: {}", id, err);
+                    }
+                    break;
+                }
+                Ok(WaitForSpot::Expired) => {
+                    let text = format!("#{} departed without a free spot", id);
+                    if let Err(err) = self.telegram.send_message(chat_id, &text).await {
+                        error!("Failed to send expiry for watch #{}: {}", id, err);
+                    }
+                    break;
+                }
+                Ok(_) => {}
+                Err(err) => warn!("Polling watch #{} failed: {}", id, err),
+            }
+        }
+        self.watch_list.lock().await.watches.remove(&id);
+    }
+}
@@ -0,0 +1,67 @@
+use crate::inputs::{input_departure_date, input_direction};
+use crate::output::BookingSetup;
+use anyhow::{anyhow, Result};
+use dialoguer::Input;
+use paat_core::booking::{flow::BookingProgress, run_doctor, steps::StepReport};
+use tokio::signal::ctrl_c;
+
+fn print_step_report(report: &StepReport) {
+    let status = if report.is_ok() { "OK" } else { "FAILED" };
+    println!(
+        "{:<18}{:<8}{:>6} ms",
+        report.step.to_string(),
+        status,
+        report.duration.as_millis()
+    );
+    for selector_match in report.matched.iter() {
+        println!(
+            "    matched {}: {}",
+            selector_match.name, selector_match.selector
+        );
+    }
+    for failed in report.failed.iter() {
+        println!("    missing {}", failed);
+    }
+    if let Some(error) = &report.error {
+        println!("    error: {}", error);
+    }
+}
+
+pub async fn run_booking_doctor(
+    booking_id: Option<String>,
+    booking_setup: &BookingSetup,
+) -> Result<()> {
+    let booking_id = match booking_id {
+        Some(booking_id) => booking_id,
+        None => Input::new().with_prompt("Booking ID").interact_text()?,
+    };
+    let direction = input_direction(None)?;
+    let departure_date = input_departure_date(None)?;
+
+    let cancel = booking_setup.options.cancel.clone();
+    tokio::spawn(async move {
+        if ctrl_c().await.is_ok() {
+            cancel.cancel();
+        }
+    });
+
+    let reports = run_doctor(
+        &booking_id,
+        &direction,
+        &departure_date,
+        &booking_setup.selectors,
+        &booking_setup.options,
+        &|progress: &BookingProgress| println!("{}", progress),
+    )
+    .await?;
+    println!();
+    for report in reports.iter() {
+        print_step_report(report);
+    }
+    if reports.iter().all(StepReport::is_ok) {
+        return Ok(());
+    }
+    Err(anyhow!(
+        "Booking automation is broken, see the failed steps above"
+    ))
+}
@@ -1,21 +1,23 @@
 use anyhow::{anyhow, Result};
 use chrono::NaiveDate;
-use dialoguer::{theme::ColorfulTheme, Input, Select};
+use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
 use paat_core::{
+    booking_client::BookingClient,
     constants::LINES,
     datetime::{get_current_date, get_naive_date, naive_date_to_input_string},
     types::{
+        booking::BookingDetails,
         event::{Event, EventMap},
         Direction,
     },
 };
 use std::{io, str::FromStr};
 
-pub fn input_departure_date() -> io::Result<NaiveDate> {
-    let current_date = get_current_date();
+pub fn input_departure_date(default: Option<NaiveDate>) -> io::Result<NaiveDate> {
+    let default_date = default.unwrap_or_else(get_current_date);
     let date_input: String = Input::new()
         .with_prompt("Departure date")
-        .default(naive_date_to_input_string(&current_date))
+        .default(naive_date_to_input_string(&default_date))
         .interact_text()?;
     let departure_date = get_naive_date(&date_input)
         .map_err(|_| io::Error::new(io::ErrorKind::Unsupported, "Unsupported date"))?;
@@ -23,7 +25,7 @@ pub fn input_departure_date() -> io::Result<NaiveDate> {
     Ok(departure_date)
 }
 
-pub fn input_booking_id() -> io::Result<Option<String>> {
+fn input_booking_id() -> io::Result<Option<String>> {
     let booking_id: String = Input::new()
         .allow_empty(true)
         .with_prompt("Booking ID (optional)")
@@ -36,9 +38,39 @@ pub fn input_booking_id() -> io::Result<Option<String>> {
     Ok(booking_id)
 }
 
-pub fn input_direction() -> io::Result<Direction> {
+pub async fn input_booking() -> Result<(Option<String>, Option<BookingDetails>)> {
+    let booking_client = BookingClient::new();
+    loop {
+        let booking_id = match input_booking_id()? {
+            Some(booking_id) => booking_id,
+            None => return Ok((None, None)),
+        };
+        match booking_client.lookup_booking(&booking_id).await {
+            Ok(booking_details) => {
+                println!("You currently hold {}", booking_details);
+                return Ok((Some(booking_id), Some(booking_details)));
+            }
+            Err(err) => {
+                println!("Failed to look up the booking: {}", err);
+                let use_anyway = Confirm::new()
+                    .with_prompt("Use this booking ID anyway?")
+                    .default(false)
+                    .interact()?;
+                if use_anyway {
+                    return Ok((Some(booking_id), None));
+                }
+            }
+        }
+    }
+}
+
+pub fn input_direction(default: Option<Direction>) -> io::Result<Direction> {
+    let default_index = (0..LINES.len())
+        .position(|index| Direction::get_line_by_index(index) == default)
+        .unwrap_or_default();
     let selection = Select::with_theme(&ColorfulTheme::default())
         .items(&LINES)
+        .default(default_index)
         .interact()?;
     let direction = Direction::from_str(LINES[selection])
         .map_err(|_| io::Error::new(io::ErrorKind::Unsupported, "Unknown direction"))?;
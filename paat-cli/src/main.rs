@@ -1,19 +1,33 @@
+#[cfg(feature = "booking")]
+mod doctor;
 mod inputs;
 mod output;
+mod report;
 
 use crate::inputs::{input_departure_date, input_direction};
 use anyhow::{anyhow, Result};
+#[cfg(feature = "booking")]
+use doctor::run_booking_doctor;
 use env_logger::init;
 use futures::StreamExt;
 use indicatif::ProgressBar;
-use inputs::{input_booking_id, input_event};
-use output::create_final_output;
+use inputs::{input_booking, input_event};
+use output::{create_final_output, BookingSetup};
 use paat_core::{
+    analytics::{AdaptiveSchedule, SpotStatistics},
     client::Client,
+    config::Config,
     constants::{TICK_TIMEOUT_DURATION, TIMEOUT_BETWEEN_REQUESTS},
+    datetime::get_service_datetime,
+    mqtt::MqttPublisher,
+    notification::Dispatcher,
+    recorder::Recorder,
     types::event::WaitForSpot,
 };
-use std::time::Duration;
+use report::print_report;
+use std::{process::exit, sync::Arc, time::Duration};
+
+const EXPIRED_EXIT_CODE: i32 = 2;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -21,20 +35,63 @@ async fn main() -> Result<()> {
         .map(|timeout| timeout.parse::<u64>().unwrap_or(TIMEOUT_BETWEEN_REQUESTS))
         .unwrap_or(TIMEOUT_BETWEEN_REQUESTS);
     init();
-    let direction = input_direction()?;
-    let departure_date = input_departure_date()?;
+    let config = Config::load()?;
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    if args.first().map(String::as_str) == Some("report") {
+        return print_report(&config);
+    }
+    let booking_setup = BookingSetup {
+        selectors: config.booking.selector_profile()?,
+        options: config.booking.options.clone(),
+        #[cfg(feature = "booking")]
+        purchase: config.booking.purchase.clone(),
+    };
+    if args
+        .iter()
+        .take(2)
+        .map(String::as_str)
+        .eq(["booking", "doctor"])
+    {
+        #[cfg(feature = "booking")]
+        return run_booking_doctor(args.get(2).cloned(), &booking_setup).await;
+        #[cfg(not(feature = "booking"))]
+        return Err(anyhow!(
+            "Booking doctor needs paat-cli built with the booking feature"
+        ));
+    }
+    let dispatcher = Dispatcher::from_config(&config.notifiers);
+    let (booking_id, booking_details) = input_booking().await?;
+    let direction = input_direction(booking_details.as_ref().map(|details| details.direction))?;
+    let departure_date =
+        input_departure_date(booking_details.as_ref().map(|details| details.date))?;
 
-    let client = Client::new(Duration::from_secs(timeout_between_requests));
+    let mut client = Client::new(Duration::from_secs(timeout_between_requests))
+        .with_stop_before_departure(config.stop_before_departure());
+    if let Some(mqtt_config) = config.mqtt {
+        client = client.with_observer(Arc::new(MqttPublisher::new(mqtt_config)?));
+    }
+    if let Some(recorder_config) = config.recorder {
+        let recorder = Recorder::new(recorder_config.clone())?;
+        if let Some(schedule_config) = config.schedule {
+            let statistics = SpotStatistics::load(&recorder_config.path)?;
+            client =
+                client.with_schedule(Arc::new(AdaptiveSchedule::new(statistics, schedule_config)));
+        }
+        client = client.with_observer(Arc::new(recorder));
+    }
     let event_map = client.fetch_events(&departure_date, &direction).await?;
 
     let selected_event = input_event(event_map)?;
-    let booking_id = input_booking_id()?;
+    let departure = get_service_datetime(&selected_event.start).ok();
 
     let progress_bar = ProgressBar::new_spinner();
     progress_bar.enable_steady_tick(*TICK_TIMEOUT_DURATION);
 
-    let mut wait_stream =
-        Box::pin(client.create_wait_stream(&departure_date, &direction, &selected_event.uuid));
+    let mut wait_stream = Box::pin(client.create_continuous_wait_stream(
+        &departure_date,
+        &direction,
+        &selected_event.uuid,
+    ));
 
     let mut wait_counter: usize = 0;
     while let Some(wait_result) = wait_stream.next().await {
@@ -42,14 +99,30 @@ async fn main() -> Result<()> {
         match wait_response {
             WaitForSpot::Done(event) => {
                 progress_bar.finish_and_clear();
-                create_final_output(&event, &direction, &departure_date, &booking_id).await?;
+                create_final_output(
+                    &event,
+                    &direction,
+                    &departure_date,
+                    &booking_id,
+                    &booking_setup,
+                    &dispatcher,
+                    config.continuous.then_some(&mut wait_stream),
+                )
+                .await?;
                 return Ok(());
             }
-            WaitForSpot::Waiting => {
+            WaitForSpot::Expired => {
+                progress_bar.finish_and_clear();
+                println!("The sailing has departed without a free spot");
+                exit(EXPIRED_EXIT_CODE);
+            }
+            WaitForSpot::Waiting | WaitForSpot::Lost(_) | WaitForSpot::Regained(_) => {
                 wait_counter += 1;
+                let pause = client.pause_before_poll(&direction, departure.as_ref());
                 progress_bar.set_message(format!(
                     "\tNumber of tries: {}, time between requests is {} seconds",
-                    wait_counter, TIMEOUT_BETWEEN_REQUESTS
+                    wait_counter,
+                    pause.as_secs()
                 ));
             }
         }
@@ -1,13 +1,28 @@
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use chrono::NaiveDate;
-use futures::{future::FutureExt, pin_mut, select};
+use futures::{
+    future::{pending, FusedFuture, FutureExt},
+    pin_mut, select, Stream, StreamExt,
+};
+use indicatif::ProgressBar;
 use log::debug;
-use paat_core::booking::change_booking;
-use paat_core::sound::play_infinite_sound;
-use paat_core::types::event::Event;
-use paat_core::types::Direction;
+use paat_core::booking::{
+    change_booking, flow::BookingProgress, options::BookingOptions, outcome::BookingOutcome,
+    selectors::SelectorProfile,
+};
+#[cfg(feature = "booking")]
+use paat_core::booking::{options::PurchaseConfig, purchase::purchase_ticket};
+use paat_core::notification::{Dispatcher, Notification, NotificationKind};
+use paat_core::types::event::{Event, WaitForSpot};
+use paat_core::{constants::TICK_TIMEOUT_DURATION, types::Direction};
 use tokio::signal::ctrl_c;
-use tokio::sync::oneshot;
+
+pub struct BookingSetup {
+    pub selectors: SelectorProfile,
+    pub options: BookingOptions,
+    #[cfg(feature = "booking")]
+    pub purchase: Option<PurchaseConfig>,
+}
 
 async fn print_end_text(event: &Event) {
     println!("Found {} spot(s)", event.capacities.small_vehicles);
@@ -20,55 +35,153 @@ async fn create_booking_future(
     event: &Event,
     direction: &Direction,
     date: &NaiveDate,
-) -> Result<()> {
+    booking_setup: &BookingSetup,
+) -> Result<Option<BookingOutcome>> {
+    let progress_bar = ProgressBar::new_spinner();
+    let on_progress = |progress: &BookingProgress| progress_bar.set_message(progress.to_string());
     let booking_id = booking_id.clone();
     if let Some(booking_id) = booking_id {
-        change_booking(&booking_id, event, direction, date).await?;
+        progress_bar.enable_steady_tick(*TICK_TIMEOUT_DURATION);
+        let outcome = change_booking(
+            &booking_id,
+            event,
+            direction,
+            date,
+            &booking_setup.selectors,
+            &booking_setup.options,
+            &on_progress,
+        )
+        .await;
+        progress_bar.finish_and_clear();
+        return outcome.map(Some);
+    }
+    #[cfg(feature = "booking")]
+    if let Some(purchase) = &booking_setup.purchase {
+        println!("Filling in a new ticket in the browser, finish the payment there");
+        progress_bar.enable_steady_tick(*TICK_TIMEOUT_DURATION);
+        let outcome = purchase_ticket(
+            event,
+            direction,
+            date,
+            &booking_setup.selectors,
+            &booking_setup.options,
+            purchase,
+            &on_progress,
+        )
+        .await;
+        progress_bar.finish_and_clear();
+        return outcome.map(Some);
+    }
+    Ok(None)
+}
+
+fn print_booking_result(booking_result: Result<Option<BookingOutcome>>) {
+    match booking_result {
+        Ok(Some(outcome)) => println!("{}", outcome),
+        Ok(None) => {}
+        Err(err) => {
+            println!("{}", err);
+            println!("Failed to change booking");
+        }
     }
-    Ok(())
 }
 
-pub async fn create_final_output(
+async fn follow_spot_changes<S>(
+    wait_stream: Option<&mut S>,
+    direction: &Direction,
+    date: &NaiveDate,
+    dispatcher: &Dispatcher,
+) where
+    S: Stream<Item = Result<WaitForSpot>> + Unpin,
+{
+    let wait_stream = match wait_stream {
+        Some(wait_stream) => wait_stream,
+        None => return pending().await,
+    };
+    while let Some(wait_result) = wait_stream.next().await {
+        let (kind, event) = match wait_result {
+            Ok(WaitForSpot::Lost(event)) => (NotificationKind::SpotLost, event),
+            Ok(WaitForSpot::Regained(event)) => (NotificationKind::SpotRegained, event),
+            Ok(WaitForSpot::Expired) => {
+                println!("The sailing has departed");
+                return;
+            }
+            Ok(_) => continue,
+            Err(err) => {
+                debug!("{}", err);
+                continue;
+            }
+        };
+        let notification = Notification::new(kind, event, *direction, *date);
+        println!("{}", notification);
+        if let Err(notification_error) = dispatcher.dispatch(&notification).await {
+            debug!("{}", notification_error)
+        }
+    }
+}
+
+pub async fn create_final_output<S>(
     event: &Event,
     direction: &Direction,
     date: &NaiveDate,
     booking_id: &Option<String>,
-) -> Result<()> {
-    let (sender, receiver) = oneshot::channel::<()>();
+    booking_setup: &BookingSetup,
+    dispatcher: &Dispatcher,
+    wait_stream: Option<&mut S>,
+) -> Result<()>
+where
+    S: Stream<Item = Result<WaitForSpot>> + Unpin,
+{
+    let notification = Notification::new(
+        NotificationKind::SpotFound,
+        event.to_owned(),
+        *direction,
+        *date,
+    )
+    .with_booking_id(booking_id.clone());
+    let cancel = booking_setup.options.cancel.clone();
     let ctrl_c_future = ctrl_c().fuse();
-    let music_future = play_infinite_sound(receiver).fuse();
+    let notification_future = dispatcher.dispatch(&notification).fuse();
     let text_future = print_end_text(event).fuse();
-    let booking_future = create_booking_future(booking_id, event, direction, date).fuse();
+    let booking_future =
+        create_booking_future(booking_id, event, direction, date, booking_setup).fuse();
+    let spot_changes_future = follow_spot_changes(wait_stream, direction, date, dispatcher).fuse();
 
-    pin_mut!(ctrl_c_future, music_future, text_future, booking_future);
+    pin_mut!(
+        ctrl_c_future,
+        notification_future,
+        text_future,
+        booking_future,
+        spot_changes_future
+    );
 
     loop {
         select! {
           _ = ctrl_c_future => {
+            // Let the booking close its browser before exiting
+            cancel.cancel();
+            if !booking_future.is_terminated() {
+              print_booking_result(booking_future.as_mut().await);
+            }
             println!();
             println!("Goodbye!");
             break
           },
-          music_result = music_future => {
-            if let Err(music_error) = music_result {
-              debug!("Failed to cancel music: {}", music_error)
+          notification_result = notification_future => {
+            if let Err(notification_error) = notification_result {
+              debug!("{}", notification_error)
             }
 
             continue
           },
           _ = text_future => continue,
+          _ = spot_changes_future => continue,
           booking_result = booking_future => {
-            if let Err(_err) = booking_result {
-              println!("{}", _err);
-              println!("Failed to change booking");
-            }
-
+            print_booking_result(booking_result);
             continue
           }
         }
     }
-    sender
-        .send(())
-        .map_err(|_| anyhow!("Failed to send message to the receiver"))?;
+    dispatcher.dismiss();
     Ok(())
 }
@@ -0,0 +1,30 @@
+use anyhow::{anyhow, Result};
+use paat_core::{analytics::SpotStatistics, config::Config};
+
+pub fn print_report(config: &Config) -> Result<()> {
+    let recorder_config = config.recorder.as_ref().ok_or_else(|| {
+        anyhow!("Capacity history is not recorded, add a [recorder] section to the config")
+    })?;
+    let report = SpotStatistics::load(&recorder_config.path)?.report();
+    if report.is_empty() {
+        println!("No sold out sailings have been recorded yet");
+        return Ok(());
+    }
+
+    println!(
+        "{:<6}{:<9}{:<14}{:>10}{:>10}{:>13}",
+        "Line", "Weekday", "Hours before", "Sailings", "Freed", "Probability"
+    );
+    for row in report {
+        println!(
+            "{:<6}{:<9}{:<14}{:>10}{:>10}{:>12.0}%",
+            row.line,
+            row.weekday.to_string(),
+            row.hours_before,
+            row.statistics.sailings,
+            row.statistics.appearances,
+            row.statistics.probability() * 100.0
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,399 @@
+use crate::{datetime::get_service_datetime, schedule::PollSchedule, types::Direction};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, FixedOffset, Utc, Weekday};
+use rusqlite::Connection;
+use serde::Deserialize;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+    time::Duration,
+};
+use strum::EnumProperty;
+
+const HOURS_BEFORE_BUCKETS: [i64; 7] = [0, 1, 2, 4, 8, 24, 48];
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ScheduleConfig {
+    #[serde(default = "default_fast_seconds")]
+    pub fast_seconds: u64,
+    #[serde(default = "default_slow_seconds")]
+    pub slow_seconds: u64,
+    #[serde(default = "default_threshold")]
+    pub threshold: f64,
+    #[serde(default = "default_min_sailings")]
+    pub min_sailings: usize,
+}
+
+fn default_fast_seconds() -> u64 {
+    10
+}
+
+fn default_slow_seconds() -> u64 {
+    120
+}
+
+fn default_threshold() -> f64 {
+    0.2
+}
+
+fn default_min_sailings() -> usize {
+    3
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WindowStatistics {
+    pub sailings: usize,
+    pub appearances: usize,
+}
+
+impl WindowStatistics {
+    pub fn probability(&self) -> f64 {
+        if self.sailings == 0 {
+            return 0.0;
+        }
+        self.appearances as f64 / self.sailings as f64
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct WindowKey {
+    line: String,
+    weekday: u32,
+    bucket: usize,
+}
+
+pub struct ReportRow {
+    pub line: String,
+    pub weekday: Weekday,
+    pub hours_before: String,
+    pub statistics: WindowStatistics,
+}
+
+struct Snapshot {
+    line: String,
+    event_uuid: String,
+    departure: DateTime<FixedOffset>,
+    recorded_at: DateTime<FixedOffset>,
+    small_vehicles: i32,
+}
+
+#[derive(Default)]
+pub struct SpotStatistics {
+    windows: BTreeMap<WindowKey, WindowStatistics>,
+}
+
+fn bucket(hours_before: i64) -> Option<usize> {
+    if hours_before < 0 {
+        return None;
+    }
+    HOURS_BEFORE_BUCKETS
+        .iter()
+        .rposition(|bucket_start| hours_before >= *bucket_start)
+}
+
+fn bucket_label(bucket: usize) -> String {
+    match HOURS_BEFORE_BUCKETS.get(bucket + 1) {
+        Some(bucket_end) => format!("{}-{}h", HOURS_BEFORE_BUCKETS[bucket], bucket_end),
+        None => format!("{}h+", HOURS_BEFORE_BUCKETS[bucket]),
+    }
+}
+
+fn hours_before(departure: &DateTime<FixedOffset>, moment: &DateTime<FixedOffset>) -> i64 {
+    departure.signed_duration_since(*moment).num_hours()
+}
+
+fn line_code(direction: &Direction) -> String {
+    direction
+        .get_str("Abbreviation")
+        .unwrap_or_default()
+        .to_string()
+}
+
+impl SpotStatistics {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let connection = Connection::open(path)
+            .with_context(|| format!("Failed to open capacity history from {}", path.display()))?;
+        Self::from_connection(&connection)
+    }
+
+    fn from_connection(connection: &Connection) -> Result<Self> {
+        let mut statement = connection.prepare(
+            "SELECT line, event_uuid, departure, recorded_at, small_vehicles
+                FROM capacity_snapshots ORDER BY event_uuid, id",
+        )?;
+        let rows = statement.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, i32>(4)?,
+            ))
+        })?;
+        let mut snapshots = Vec::new();
+        for row in rows {
+            let (line, event_uuid, departure, recorded_at, small_vehicles) = row?;
+            if let (Ok(departure), Ok(recorded_at)) = (
+                get_service_datetime(&departure),
+                DateTime::parse_from_rfc3339(&recorded_at),
+            ) {
+                snapshots.push(Snapshot {
+                    line,
+                    event_uuid,
+                    departure,
+                    recorded_at,
+                    small_vehicles,
+                });
+            }
+        }
+
+        let mut statistics = Self::default();
+        let mut sailing_start = 0;
+        for index in 1..=snapshots.len() {
+            if index == snapshots.len()
+                || snapshots[index].event_uuid != snapshots[sailing_start].event_uuid
+            {
+                statistics.add_sailing(&snapshots[sailing_start..index]);
+                sailing_start = index;
+            }
+        }
+        Ok(statistics)
+    }
+
+    fn add_sailing(&mut self, snapshots: &[Snapshot]) {
+        let first = match snapshots.first() {
+            Some(first) => first,
+            None => return,
+        };
+        if snapshots.iter().all(|snapshot| snapshot.small_vehicles > 0) {
+            return;
+        }
+        let first_bucket = match bucket(hours_before(&first.departure, &first.recorded_at)) {
+            Some(first_bucket) => first_bucket,
+            None => return,
+        };
+        let appearance_buckets = snapshots
+            .windows(2)
+            .filter(|pair| pair[0].small_vehicles <= 0 && pair[1].small_vehicles > 0)
+            .filter_map(|pair| bucket(hours_before(&pair[1].departure, &pair[1].recorded_at)))
+            .collect::<BTreeSet<usize>>();
+        let weekday = first.departure.weekday().num_days_from_monday();
+        for observed_bucket in 0..=first_bucket {
+            let window = self
+                .windows
+                .entry(WindowKey {
+                    line: first.line.clone(),
+                    weekday,
+                    bucket: observed_bucket,
+                })
+                .or_default();
+            window.sailings += 1;
+            if appearance_buckets.contains(&observed_bucket) {
+                window.appearances += 1;
+            }
+        }
+    }
+
+    pub fn window(
+        &self,
+        direction: &Direction,
+        departure: &DateTime<FixedOffset>,
+        moment: &DateTime<FixedOffset>,
+    ) -> Option<WindowStatistics> {
+        let key = WindowKey {
+            line: line_code(direction),
+            weekday: departure.weekday().num_days_from_monday(),
+            bucket: bucket(hours_before(departure, moment))?,
+        };
+        self.windows.get(&key).copied()
+    }
+
+    pub fn report(&self) -> Vec<ReportRow> {
+        self.windows
+            .iter()
+            .map(|(key, statistics)| ReportRow {
+                line: key.line.clone(),
+                weekday: WEEKDAYS[key.weekday as usize],
+                hours_before: bucket_label(key.bucket),
+                statistics: *statistics,
+            })
+            .collect()
+    }
+}
+
+pub struct AdaptiveSchedule {
+    statistics: SpotStatistics,
+    config: ScheduleConfig,
+}
+
+impl AdaptiveSchedule {
+    pub fn new(statistics: SpotStatistics, config: ScheduleConfig) -> Self {
+        Self { statistics, config }
+    }
+}
+
+impl AdaptiveSchedule {
+    fn pause_at(
+        &self,
+        direction: &Direction,
+        departure: &DateTime<FixedOffset>,
+        now: &DateTime<FixedOffset>,
+    ) -> Option<Duration> {
+        let window = self.statistics.window(direction, departure, now)?;
+        if window.sailings < self.config.min_sailings {
+            return None;
+        }
+        if window.probability() >= self.config.threshold {
+            return Some(Duration::from_secs(self.config.fast_seconds));
+        }
+        Some(Duration::from_secs(self.config.slow_seconds))
+    }
+}
+
+impl PollSchedule for AdaptiveSchedule {
+    fn pause(&self, direction: &Direction, departure: &DateTime<FixedOffset>) -> Option<Duration> {
+        let now = Utc::now().with_timezone(departure.offset());
+        self.pause_at(direction, departure, &now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recorder::CREATE_TABLE;
+    use chrono::Duration as ChronoDuration;
+
+    const FIRST_DEPARTURE: &str = "2026-07-03T17:30:00.000+0300";
+    const SECOND_DEPARTURE: &str = "2026-07-10T17:30:00.000+0300";
+    const THIRD_DEPARTURE: &str = "2026-07-17T17:30:00.000+0300";
+
+    fn insert_snapshot(
+        connection: &Connection,
+        event_uuid: &str,
+        departure: &str,
+        hours_before: i64,
+        small_vehicles: i32,
+    ) {
+        let recorded_at =
+            get_service_datetime(departure).unwrap() - ChronoDuration::hours(hours_before);
+        connection
+            .execute(
+                "INSERT INTO capacity_snapshots (recorded_at, line, departure_date, event_uuid,
+                    departure, passengers, bc, small_vehicles, large_vehicles, dc, status)
+                    VALUES (?1, 'VK', '', ?2, ?3, 120, 4, ?4, 0, 1, 'IN_SALE')",
+                rusqlite::params![
+                    recorded_at.to_rfc3339(),
+                    event_uuid,
+                    departure,
+                    small_vehicles
+                ],
+            )
+            .unwrap();
+    }
+
+    fn create_statistics() -> SpotStatistics {
+        let connection = Connection::open_in_memory().unwrap();
+        connection.execute_batch(CREATE_TABLE).unwrap();
+        // A spot shows up 3 hours before the first sailing.
+        insert_snapshot(&connection, "first", FIRST_DEPARTURE, 50, 0);
+        insert_snapshot(&connection, "first", FIRST_DEPARTURE, 10, 0);
+        insert_snapshot(&connection, "first", FIRST_DEPARTURE, 3, 2);
+        // The second sailing is first seen 5 hours before and never gets a spot.
+        insert_snapshot(&connection, "second", SECOND_DEPARTURE, 5, 0);
+        insert_snapshot(&connection, "second", SECOND_DEPARTURE, 1, 0);
+        // The third sailing always has spots and tells nothing about appearing spots.
+        insert_snapshot(&connection, "third", THIRD_DEPARTURE, 30, 1);
+        insert_snapshot(&connection, "third", THIRD_DEPARTURE, 2, 3);
+        SpotStatistics::from_connection(&connection).unwrap()
+    }
+
+    fn window_at(statistics: &SpotStatistics, hours_before: i64) -> Option<WindowStatistics> {
+        let departure = get_service_datetime(FIRST_DEPARTURE).unwrap();
+        let moment = departure - ChronoDuration::hours(hours_before);
+        statistics.window(&Direction::VK, &departure, &moment)
+    }
+
+    fn create_schedule(min_sailings: usize) -> AdaptiveSchedule {
+        AdaptiveSchedule::new(
+            create_statistics(),
+            ScheduleConfig {
+                fast_seconds: 10,
+                slow_seconds: 120,
+                threshold: 0.5,
+                min_sailings,
+            },
+        )
+    }
+
+    fn pause_at(schedule: &AdaptiveSchedule, hours_before: i64) -> Option<Duration> {
+        let departure = get_service_datetime(FIRST_DEPARTURE).unwrap();
+        let now = departure - ChronoDuration::hours(hours_before);
+        schedule.pause_at(&Direction::VK, &departure, &now)
+    }
+
+    #[test]
+    fn buckets_hours_before_departure() {
+        assert_eq!(bucket(-1), None);
+        assert_eq!(bucket(0), Some(0));
+        assert_eq!(bucket(1), Some(1));
+        assert_eq!(bucket(3), Some(2));
+        assert_eq!(bucket(4), Some(3));
+        assert_eq!(bucket(47), Some(5));
+        assert_eq!(bucket(48), Some(6));
+        assert_eq!(bucket(500), Some(6));
+        assert_eq!(bucket_label(0), "0-1h");
+        assert_eq!(bucket_label(5), "24-48h");
+        assert_eq!(bucket_label(6), "48h+");
+    }
+
+    #[test]
+    fn counts_sailings_and_appearances_per_window() {
+        let statistics = create_statistics();
+
+        let window = window_at(&statistics, 3).unwrap();
+        assert_eq!(window.sailings, 2);
+        assert_eq!(window.appearances, 1);
+        assert_eq!(window.probability(), 0.5);
+
+        let window = window_at(&statistics, 5).unwrap();
+        assert_eq!(window.sailings, 2);
+        assert_eq!(window.appearances, 0);
+
+        let window = window_at(&statistics, 30).unwrap();
+        assert_eq!(window.sailings, 1);
+        assert_eq!(window.probability(), 0.0);
+
+        assert_eq!(
+            window_at(&statistics, -1).map(|window| window.sailings),
+            None
+        );
+        assert_eq!(statistics.report().len(), HOURS_BEFORE_BUCKETS.len());
+    }
+
+    #[test]
+    fn probability_of_empty_window_is_zero() {
+        assert_eq!(WindowStatistics::default().probability(), 0.0);
+    }
+
+    #[test]
+    fn adaptive_schedule_polls_fast_only_in_likely_windows() {
+        let schedule = create_schedule(2);
+        assert_eq!(pause_at(&schedule, 3), Some(Duration::from_secs(10)));
+        assert_eq!(pause_at(&schedule, 5), Some(Duration::from_secs(120)));
+        // Only the first sailing was watched this early, too few to decide.
+        assert_eq!(pause_at(&schedule, 30), None);
+
+        let schedule = create_schedule(3);
+        assert_eq!(pause_at(&schedule, 3), None);
+    }
+}
@@ -0,0 +1,412 @@
+use anyhow::{anyhow, Result};
+use chromiumoxide::{
+    browser::{Browser, BrowserConfig},
+    page::ScreenshotParams,
+    Element, Page,
+};
+use chrono::NaiveDate;
+use futures::StreamExt;
+use log::{info, warn};
+use std::{fs, path::PathBuf, time::Duration};
+use tokio::{
+    task::JoinHandle,
+    time::{sleep, Instant},
+};
+
+use super::{
+    flow::OnProgress,
+    options::{ArtifactMode, BookingOptions},
+    outcome::BookingOutcome,
+    runner::{run_plan, BookingTarget, CHANGE_PLAN, DOCTOR_PLAN},
+    selectors::SelectorProfile,
+    steps::{BookingStep, StepLog, StepReport},
+};
+use crate::{
+    datetime::naive_date_to_input_string,
+    types::{event::Event, Direction},
+    url::booking_url,
+};
+
+const ELEMENT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+pub(super) struct BookingSession<'a> {
+    pub(super) page: Page,
+    pub(super) selectors: &'a SelectorProfile,
+    pub(super) options: &'a BookingOptions,
+    pub(super) log: StepLog,
+    pub(super) artifacts_directory: Option<PathBuf>,
+}
+
+impl<'a> BookingSession<'a> {
+    pub(super) fn new(
+        page: Page,
+        selectors: &'a SelectorProfile,
+        options: &'a BookingOptions,
+    ) -> Self {
+        let artifacts_directory = options.artifacts_directory.as_ref().map(|directory| {
+            directory.join(chrono::Local::now().format("%Y%m%d-%H%M%S").to_string())
+        });
+        Self {
+            page,
+            selectors,
+            options,
+            log: StepLog::default(),
+            artifacts_directory,
+        }
+    }
+
+    pub(super) fn begin(&mut self, step: BookingStep) {
+        self.log.begin(step);
+    }
+
+    pub(super) async fn finish<T>(&mut self, result: Result<T>) -> Result<T> {
+        let result = self.log.finish(result);
+        let is_ok = self.log.last().map(StepReport::is_ok).unwrap_or(true);
+        if self.options.artifacts == ArtifactMode::EachStep || !is_ok {
+            if let Err(err) = self.save_artifacts().await {
+                warn!("Failed to save booking artifacts: {}", err);
+            }
+        }
+        result
+    }
+
+    pub(super) async fn save_artifacts(&self) -> Result<()> {
+        let (directory, report) = match (&self.artifacts_directory, self.log.last()) {
+            (Some(directory), Some(report)) => (directory, report),
+            _ => return Ok(()),
+        };
+        fs::create_dir_all(directory)?;
+        let name = format!(
+            "{:02}-{}",
+            self.log.len(),
+            report.step.to_string().replace(' ', "-")
+        );
+        let screenshot = self
+            .page
+            .screenshot(ScreenshotParams::builder().full_page(true).build())
+            .await?;
+        fs::write(directory.join(format!("{}.png", name)), screenshot)?;
+        let content = self.page.content().await?;
+        fs::write(directory.join(format!("{}.html", name)), content)?;
+        Ok(())
+    }
+
+    pub(super) async fn wait_for_element_within(
+        &mut self,
+        name: &'static str,
+        selectors: &[String],
+        timeout: Duration,
+    ) -> Option<Element> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            for selector in selectors {
+                let element = self.page.find_element(selector.as_str()).await;
+                if let Ok(element) = element {
+                    if let Ok(_) = element.scroll_into_view().await {
+                        self.log.record(name, Some(selector));
+                        return Some(element);
+                    }
+                }
+            }
+            if Instant::now() > deadline || self.options.cancel.is_cancelled() {
+                self.log.record(name, None);
+                return None;
+            }
+            sleep(ELEMENT_POLL_INTERVAL).await;
+        }
+    }
+
+    pub(super) async fn require_element(
+        &mut self,
+        name: &'static str,
+        selectors: &[String],
+    ) -> Result<Element> {
+        self.require_element_within(name, selectors, self.options.step_timeout())
+            .await
+    }
+
+    pub(super) async fn require_element_within(
+        &mut self,
+        name: &'static str,
+        selectors: &[String],
+        timeout: Duration,
+    ) -> Result<Element> {
+        self.wait_for_element_within(name, selectors, timeout)
+            .await
+            .ok_or_else(|| anyhow!("Failed to find {}", name))
+    }
+
+    pub(super) fn failed_outcome(&self, err: anyhow::Error) -> BookingOutcome {
+        warn!("Booking failed: {}", err);
+        BookingOutcome::StepFailed {
+            step: self
+                .log
+                .last()
+                .map(|report| report.step)
+                .unwrap_or(BookingStep::OpenChange),
+            reason: err.to_string(),
+        }
+    }
+
+    pub(super) async fn find_elements(
+        &mut self,
+        name: &'static str,
+        selectors: &[String],
+    ) -> Result<Vec<Element>> {
+        for selector in selectors {
+            let elements = self.page.find_elements(selector.as_str()).await?;
+            if !elements.is_empty() {
+                self.log.record(name, Some(selector));
+                return Ok(elements);
+            }
+        }
+        self.log.record(name, None);
+        Err(anyhow!("Failed to find {}", name))
+    }
+
+    pub(super) async fn find_child_element(
+        &mut self,
+        element: &Element,
+        name: &'static str,
+        selectors: &[String],
+    ) -> Result<Element> {
+        for selector in selectors {
+            if let Ok(child) = element.find_element(selector.as_str()).await {
+                self.log.record(name, Some(selector));
+                return Ok(child);
+            }
+        }
+        self.log.record(name, None);
+        Err(anyhow!("Failed to find {}", name))
+    }
+}
+
+pub(super) async fn open_booking_change(session: &mut BookingSession<'_>) -> Result<()> {
+    let selectors = session.selectors;
+    session
+        .require_element("change_button", &selectors.change_button)
+        .await?
+        .click()
+        .await?;
+    Ok(())
+}
+
+pub(super) async fn select_desired_direction(
+    session: &mut BookingSession<'_>,
+    desired_direction: &Direction,
+) -> Result<()> {
+    let selectors = session.selectors;
+    session
+        .require_element("direction_select", &selectors.direction_select)
+        .await?
+        .click()
+        .await?;
+
+    let option_selectors = selectors.direction_option(desired_direction);
+    session
+        .require_element("direction_option", &option_selectors)
+        .await?
+        .click()
+        .await?;
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum RowSelection {
+    Selected,
+    NotFound,
+    SoldOut,
+}
+
+pub(super) async fn select_right_booking_type(
+    session: &mut BookingSession<'_>,
+    event: Option<&Event>,
+) -> Result<RowSelection> {
+    let selectors = session.selectors;
+    session
+        .require_element("booking_block", &selectors.booking_block)
+        .await?;
+    let booking_rows = session
+        .find_elements("booking_row", &selectors.booking_row)
+        .await?;
+
+    for element in booking_rows {
+        let departure_time = session
+            .find_child_element(&element, "departure_time", &selectors.departure_time)
+            .await?;
+        let departure_time_text = departure_time.inner_text().await?;
+        if let Some(departure_time_text) = departure_time_text {
+            let departure_time_text = departure_time_text.trim();
+            let is_desired_event = event
+                .map(|event| event.to_string().starts_with(departure_time_text))
+                .unwrap_or(true);
+            if is_desired_event {
+                let booking_button = session
+                    .find_child_element(&element, "booking_button", &selectors.booking_button)
+                    .await?;
+                if event.is_some() && booking_button.attribute("disabled").await?.is_some() {
+                    return Ok(RowSelection::SoldOut);
+                }
+                booking_button.scroll_into_view().await?;
+                booking_button.click().await?;
+                return Ok(RowSelection::Selected);
+            }
+        }
+    }
+
+    Ok(RowSelection::NotFound)
+}
+
+pub(super) async fn launch_browser(options: &BookingOptions) -> Result<(Browser, JoinHandle<()>)> {
+    let mut builder = BrowserConfig::builder().incognito();
+    if !options.headless {
+        builder = builder.with_head();
+    }
+    if let Some(executable) = &options.executable {
+        builder = builder.chrome_executable(executable);
+    }
+    let (browser, mut handler) = Browser::launch(builder.build().map_err(|err| {
+        anyhow::anyhow!(
+            "Failed to create browser with following configuration: {:?}",
+            err
+        )
+    })?)
+    .await?;
+
+    let handle = tokio::task::spawn(async move { while handler.next().await.is_some() {} });
+    Ok((browser, handle))
+}
+
+pub(super) async fn open_session<'a>(
+    browser: &Browser,
+    url: &str,
+    selectors: &'a SelectorProfile,
+    options: &'a BookingOptions,
+) -> Result<BookingSession<'a>> {
+    let page = browser.new_page(url).await?;
+    page.bring_to_front().await?;
+    Ok(BookingSession::new(page, selectors, options))
+}
+
+pub(super) async fn press_continue(session: &mut BookingSession<'_>) -> Result<()> {
+    let selectors = session.selectors;
+    let continue_button = session
+        .require_element("continue_button", &selectors.continue_button)
+        .await?;
+    if session.options.dry_run {
+        info!("Dry run, not pressing the continue button");
+        return Ok(());
+    }
+    sleep(Duration::from_millis(500)).await;
+    continue_button.scroll_into_view().await?;
+    sleep(Duration::from_millis(1500)).await;
+    continue_button.click().await?;
+    Ok(())
+}
+
+pub(super) async fn read_confirmation(
+    session: &mut BookingSession<'_>,
+    event: &Event,
+    date: &NaiveDate,
+) -> Result<String> {
+    let selectors = session.selectors;
+    let confirmed_departure = session
+        .require_element_within(
+            "confirmed_departure",
+            &selectors.confirmed_departure,
+            session.options.confirmation_timeout(),
+        )
+        .await?
+        .inner_text()
+        .await?
+        .unwrap_or_default();
+    let confirmed_departure = confirmed_departure.trim();
+    let confirmed_date = session
+        .require_element("confirmed_date", &selectors.confirmed_date)
+        .await?
+        .inner_text()
+        .await?
+        .unwrap_or_default();
+    let confirmed_date = confirmed_date.trim();
+    let desired_date = naive_date_to_input_string(date);
+    if confirmed_departure.is_empty()
+        || !event.to_string().starts_with(confirmed_departure)
+        || !confirmed_date.contains(&desired_date)
+    {
+        return Err(anyhow!(
+            "Booking still shows the {} {} departure after confirming",
+            confirmed_date,
+            confirmed_departure
+        ));
+    }
+    Ok(confirmed_departure.to_string())
+}
+
+pub(super) async fn close_browser(browser: &mut Browser, handle: JoinHandle<()>) {
+    if let Err(err) = browser.close().await {
+        warn!("Failed to close the browser: {}", err);
+    }
+    if let Err(err) = browser.wait().await {
+        warn!("Failed to wait for the browser to exit: {}", err);
+    }
+    handle.abort();
+}
+
+pub async fn change_booking_in_browser(
+    booking_id: &str,
+    event: &Event,
+    direction: &Direction,
+    date: &NaiveDate,
+    selectors: &SelectorProfile,
+    options: &BookingOptions,
+    on_progress: &OnProgress<'_>,
+) -> Result<BookingOutcome> {
+    let (mut browser, handle) = launch_browser(options).await?;
+
+    let target = BookingTarget {
+        event: Some(event),
+        direction,
+        date,
+        purchase: None,
+    };
+    let outcome = match open_session(&browser, &booking_url(booking_id), selectors, options).await {
+        Ok(mut session) => run_plan(&mut session, &CHANGE_PLAN, &target, on_progress, true)
+            .await
+            .ok_or_else(|| anyhow!("Booking change finished without an outcome")),
+        Err(err) => Err(err),
+    };
+
+    close_browser(&mut browser, handle).await;
+    outcome
+}
+
+pub async fn run_doctor(
+    booking_id: &str,
+    direction: &Direction,
+    date: &NaiveDate,
+    selectors: &SelectorProfile,
+    options: &BookingOptions,
+    on_progress: &OnProgress<'_>,
+) -> Result<Vec<StepReport>> {
+    let options = options.clone().with_dry_run(true);
+    let (mut browser, handle) = launch_browser(&options).await?;
+
+    let target = BookingTarget {
+        event: None,
+        direction,
+        date,
+        purchase: None,
+    };
+    let reports = match open_session(&browser, &booking_url(booking_id), selectors, &options).await
+    {
+        Ok(mut session) => {
+            run_plan(&mut session, &DOCTOR_PLAN, &target, on_progress, false).await;
+            Ok(session.log.into_reports())
+        }
+        Err(err) => Err(err),
+    };
+
+    close_browser(&mut browser, handle).await;
+    reports
+}
@@ -0,0 +1,215 @@
+use super::browser::BookingSession;
+use crate::datetime::get_current_date;
+use anyhow::{anyhow, Result};
+use chromiumoxide::Element;
+use chrono::{Datelike, NaiveDate};
+use std::cmp::Ordering;
+
+const MAX_MONTH_MOVES: usize = 36;
+
+// Month names as shown by the date picker in every portal language
+const MONTH_NAMES: [[&str; 12]; 3] = [
+    [
+        "jaanuar",
+        "veebruar",
+        "märts",
+        "aprill",
+        "mai",
+        "juuni",
+        "juuli",
+        "august",
+        "september",
+        "oktoober",
+        "november",
+        "detsember",
+    ],
+    [
+        "january",
+        "february",
+        "march",
+        "april",
+        "may",
+        "june",
+        "july",
+        "august",
+        "september",
+        "october",
+        "november",
+        "december",
+    ],
+    [
+        "январь",
+        "февраль",
+        "март",
+        "апрель",
+        "май",
+        "июнь",
+        "июль",
+        "август",
+        "сентябрь",
+        "октябрь",
+        "ноябрь",
+        "декабрь",
+    ],
+];
+
+const READ_SELECTED_DATE_FUNCTION: &str = "function() {
+    const picker = this._flatpickr;
+    if (!picker || picker.selectedDates.length === 0) {
+        return null;
+    }
+    const date = picker.selectedDates[0];
+    return date.getFullYear() + '-' + (date.getMonth() + 1) + '-' + date.getDate();
+}";
+
+fn parse_month0(month_text: &str) -> Option<u32> {
+    let month_text = month_text.trim().to_lowercase();
+    MONTH_NAMES.iter().find_map(|month_names| {
+        month_names
+            .iter()
+            .position(|month_name| *month_name == month_text)
+            .map(|position| position as u32)
+    })
+}
+
+async fn set_date_with_flatpickr(date_input: &Element, desired_date: &NaiveDate) -> Result<bool> {
+    let set_date_function = format!(
+        "function() {{
+            if (!this._flatpickr) {{
+                return false;
+            }}
+            this._flatpickr.setDate(new Date({}, {}, {}), true);
+            return true;
+        }}",
+        desired_date.year(),
+        desired_date.month0(),
+        desired_date.day()
+    );
+    let returns = date_input.call_js_fn(set_date_function, false).await?;
+    Ok(returns
+        .result
+        .value
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false))
+}
+
+async fn read_selected_date(date_input: &Element) -> Result<Option<NaiveDate>> {
+    let returns = date_input
+        .call_js_fn(READ_SELECTED_DATE_FUNCTION, false)
+        .await?;
+    Ok(returns
+        .result
+        .value
+        .as_ref()
+        .and_then(|value| value.as_str())
+        .and_then(|selected_date| NaiveDate::parse_from_str(selected_date, "%Y-%m-%d").ok()))
+}
+
+async fn read_shown_month(session: &mut BookingSession<'_>) -> Result<i32> {
+    let selectors = session.selectors;
+    let selected_year = session
+        .require_element("selected_year", &selectors.selected_year)
+        .await?;
+    let year_text = selected_year
+        .property("value")
+        .await?
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default();
+    let year = year_text
+        .parse::<i32>()
+        .map_err(|_| anyhow!("Failed to parse year from text: {}", year_text))?;
+
+    let selected_month = session
+        .require_element("selected_month", &selectors.selected_month)
+        .await?;
+    // Newer date pickers show the month in a select whose value is the month index
+    let month_value = selected_month
+        .property("value")
+        .await?
+        .and_then(|value| value.as_str().and_then(|value| value.parse::<u32>().ok()));
+    let month0 = match month_value {
+        Some(month0) => month0,
+        None => {
+            let month_text = selected_month.inner_text().await?.unwrap_or_default();
+            parse_month0(&month_text)
+                .ok_or_else(|| anyhow!("Failed to parse month from text: {}", month_text))?
+        }
+    };
+
+    Ok(year * 12 + month0 as i32)
+}
+
+async fn move_to_month(session: &mut BookingSession<'_>, desired_date: &NaiveDate) -> Result<()> {
+    let selectors = session.selectors;
+    let desired_month = desired_date.year() * 12 + desired_date.month0() as i32;
+    for _ in 0..MAX_MONTH_MOVES {
+        let (name, button_selectors) = match read_shown_month(session).await?.cmp(&desired_month) {
+            Ordering::Equal => return Ok(()),
+            Ordering::Less => ("next_month", &selectors.next_month),
+            Ordering::Greater => ("previous_month", &selectors.previous_month),
+        };
+        session
+            .require_element(name, button_selectors)
+            .await?
+            .click()
+            .await?;
+    }
+    Err(anyhow!(
+        "Failed to move the date picker to {}",
+        desired_date.format("%Y-%m")
+    ))
+}
+
+async fn select_booking_date(
+    session: &mut BookingSession<'_>,
+    desired_date: &NaiveDate,
+) -> Result<()> {
+    let selectors = session.selectors;
+    let dates = session
+        .find_elements("booking_date", &selectors.booking_date)
+        .await?;
+    for date in dates {
+        let date_text = date.inner_text().await?;
+        // Padding and header cells of the calendar have no day number
+        let date_number = date_text.and_then(|date_text| date_text.trim().parse::<u32>().ok());
+        if date_number == Some(desired_date.day()) {
+            date.click().await?;
+            return Ok(());
+        }
+    }
+
+    Err(anyhow!(
+        "Failed to find day {} in the date picker",
+        desired_date.day()
+    ))
+}
+
+pub(super) async fn select_desired_date(
+    session: &mut BookingSession<'_>,
+    desired_date: &NaiveDate,
+) -> Result<()> {
+    let selectors = session.selectors;
+    let current_date = get_current_date();
+    if desired_date < &current_date {
+        return Err(anyhow::anyhow!("Cannot book into past"));
+    }
+
+    let date_input = session
+        .require_element("date_select", &selectors.date_select)
+        .await?;
+    if !set_date_with_flatpickr(&date_input, desired_date).await? {
+        date_input.click().await?;
+        move_to_month(session, desired_date).await?;
+        select_booking_date(session, desired_date).await?;
+    }
+
+    match read_selected_date(&date_input).await? {
+        Some(selected_date) if selected_date == *desired_date => Ok(()),
+        Some(selected_date) => Err(anyhow!(
+            "Date picker selected {} instead of {}",
+            selected_date,
+            desired_date
+        )),
+        None => Err(anyhow!("Failed to verify the selected date")),
+    }
+}
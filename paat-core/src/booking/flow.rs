@@ -0,0 +1,28 @@
+use super::steps::BookingStep;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BookingProgress {
+    pub step: BookingStep,
+    pub position: usize,
+    pub total: usize,
+    pub attempt: u32,
+}
+
+impl Display for BookingProgress {
+    fn fmt(&self, fmt: &mut Formatter) -> std::fmt::Result {
+        write!(
+            fmt,
+            "Step {}/{}: {}",
+            self.position,
+            self.total,
+            self.step.action()
+        )?;
+        if self.attempt > 1 {
+            write!(fmt, " (attempt {})", self.attempt)?;
+        }
+        Ok(())
+    }
+}
+
+pub type OnProgress<'a> = dyn Fn(&BookingProgress) + Send + Sync + 'a;
@@ -0,0 +1,108 @@
+use anyhow::Result;
+use chrono::NaiveDate;
+use log::warn;
+use serde::Deserialize;
+use std::path::PathBuf;
+
+use crate::{
+    booking_client::{BookingClient, TicketChange},
+    datetime::service_datetime_to_local_time_string,
+    types::{event::Event, Direction},
+};
+
+#[cfg(feature = "booking")]
+mod browser;
+#[cfg(feature = "booking")]
+mod date_picker;
+pub mod flow;
+pub mod options;
+pub mod outcome;
+#[cfg(feature = "booking")]
+pub mod purchase;
+#[cfg(feature = "booking")]
+mod runner;
+pub mod selectors;
+pub mod steps;
+
+#[cfg(feature = "booking")]
+pub use browser::{change_booking_in_browser, run_doctor};
+use flow::OnProgress;
+use options::{BookingOptions, PurchaseConfig};
+use outcome::BookingOutcome;
+use selectors::SelectorProfile;
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct BookingConfig {
+    pub selectors: Option<PathBuf>,
+    #[serde(flatten)]
+    pub options: BookingOptions,
+    pub purchase: Option<PurchaseConfig>,
+    #[serde(default)]
+    pub auto_change: bool,
+}
+
+impl BookingConfig {
+    pub fn selector_profile(&self) -> Result<SelectorProfile> {
+        SelectorProfile::load(self.selectors.as_ref())
+    }
+}
+
+pub async fn change_booking(
+    booking_id: &str,
+    event: &Event,
+    direction: &Direction,
+    date: &NaiveDate,
+    selectors: &SelectorProfile,
+    options: &BookingOptions,
+    on_progress: &OnProgress<'_>,
+) -> Result<BookingOutcome> {
+    if options.cancel.is_cancelled() {
+        return Ok(BookingOutcome::Cancelled);
+    }
+    if !options.dry_run {
+        match BookingClient::new()
+            .change_booking(booking_id, &event.uuid)
+            .await
+        {
+            TicketChange::Changed(ticket) => {
+                let new_departure = service_datetime_to_local_time_string(&ticket.event.start)
+                    .unwrap_or(ticket.event.start);
+                return Ok(BookingOutcome::Changed { new_departure });
+            }
+            TicketChange::Unverified(err) => {
+                return Ok(BookingOutcome::Unverified {
+                    reason: err.to_string(),
+                })
+            }
+            TicketChange::NotAccepted(err) => warn!(
+                "Failed to change booking through the API, falling back to the browser: {}",
+                err
+            ),
+        }
+    }
+    change_booking_in_browser(
+        booking_id,
+        event,
+        direction,
+        date,
+        selectors,
+        options,
+        on_progress,
+    )
+    .await
+}
+
+#[cfg(not(feature = "booking"))]
+async fn change_booking_in_browser(
+    _booking_id: &str,
+    _event: &Event,
+    _direction: &Direction,
+    _date: &NaiveDate,
+    _selectors: &SelectorProfile,
+    _options: &BookingOptions,
+    _on_progress: &OnProgress<'_>,
+) -> Result<BookingOutcome> {
+    Err(anyhow::anyhow!(
+        "Changing the booking in a browser needs the booking feature"
+    ))
+}
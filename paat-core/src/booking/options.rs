@@ -0,0 +1,72 @@
+use serde::Deserialize;
+use std::{path::PathBuf, time::Duration};
+use tokio_util::sync::CancellationToken;
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum ArtifactMode {
+    #[default]
+    Failure,
+    EachStep,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct BookingOptions {
+    pub headless: bool,
+    pub executable: Option<PathBuf>,
+    pub dry_run: bool,
+    pub step_timeout_seconds: u64,
+    pub step_retries: u32,
+    pub confirmation_timeout_seconds: u64,
+    pub artifacts_directory: Option<PathBuf>,
+    pub artifacts: ArtifactMode,
+    #[serde(skip)]
+    pub cancel: CancellationToken,
+}
+
+impl Default for BookingOptions {
+    fn default() -> Self {
+        Self {
+            headless: false,
+            executable: None,
+            dry_run: false,
+            step_timeout_seconds: 5,
+            step_retries: 1,
+            confirmation_timeout_seconds: 30,
+            artifacts_directory: None,
+            artifacts: ArtifactMode::default(),
+            cancel: CancellationToken::new(),
+        }
+    }
+}
+
+impl BookingOptions {
+    pub fn step_timeout(&self) -> Duration {
+        Duration::from_secs(self.step_timeout_seconds)
+    }
+
+    pub fn confirmation_timeout(&self) -> Duration {
+        Duration::from_secs(self.confirmation_timeout_seconds)
+    }
+
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct PurchaseConfig {
+    pub vehicle_category: String,
+    pub plate_number: String,
+    #[serde(default = "default_adults")]
+    pub adults: u32,
+    #[serde(default)]
+    pub children: u32,
+    pub email: String,
+}
+
+fn default_adults() -> u32 {
+    1
+}
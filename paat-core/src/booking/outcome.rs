@@ -0,0 +1,51 @@
+use super::steps::BookingStep;
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BookingOutcome {
+    Changed { new_departure: String },
+    DryRun,
+    ReadyForPayment,
+    StepFailed { step: BookingStep, reason: String },
+    NoMatchingRow,
+    SpotGoneBeforeConfirm,
+    Cancelled,
+    Unverified { reason: String },
+}
+
+impl BookingOutcome {
+    pub fn is_changed(&self) -> bool {
+        matches!(self, BookingOutcome::Changed { .. })
+    }
+}
+
+impl Display for BookingOutcome {
+    fn fmt(&self, fmt: &mut Formatter) -> Result {
+        match self {
+            BookingOutcome::Changed { new_departure } => {
+                write!(fmt, "Booking changed to the {} departure", new_departure)
+            }
+            BookingOutcome::DryRun => {
+                fmt.write_str("Dry run finished before confirming the change")
+            }
+            BookingOutcome::ReadyForPayment => {
+                fmt.write_str("The new ticket was filled in and handed over for payment")
+            }
+            BookingOutcome::StepFailed { step, reason } => {
+                write!(fmt, "Booking change failed at {}: {}", step, reason)
+            }
+            BookingOutcome::NoMatchingRow => {
+                fmt.write_str("The sailing was not listed when changing the booking")
+            }
+            BookingOutcome::SpotGoneBeforeConfirm => {
+                fmt.write_str("The spot was taken before the change could be confirmed")
+            }
+            BookingOutcome::Cancelled => fmt.write_str("Booking was stopped before it finished"),
+            BookingOutcome::Unverified { reason } => write!(
+                fmt,
+                "The booking change was sent but could not be confirmed, verify the booking: {}",
+                reason
+            ),
+        }
+    }
+}
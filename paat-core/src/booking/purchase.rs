@@ -0,0 +1,139 @@
+use super::{
+    browser::{close_browser, launch_browser, open_session, BookingSession},
+    flow::OnProgress,
+    options::{BookingOptions, PurchaseConfig},
+    outcome::BookingOutcome,
+    runner::{run_plan, BookingTarget, PURCHASE_PLAN},
+    selectors::SelectorProfile,
+};
+use crate::{
+    types::{event::Event, Direction},
+    url::purchase_url,
+};
+use anyhow::{anyhow, Result};
+use chrono::NaiveDate;
+use log::info;
+use tokio::select;
+
+async fn fill_input(
+    session: &mut BookingSession<'_>,
+    name: &'static str,
+    selectors: &[String],
+    value: &str,
+) -> Result<()> {
+    let input = session.require_element(name, selectors).await?;
+    input.click().await?;
+    input.type_str(value).await?;
+    Ok(())
+}
+
+pub(super) async fn select_vehicle(
+    session: &mut BookingSession<'_>,
+    purchase: &PurchaseConfig,
+) -> Result<()> {
+    let selectors = session.selectors;
+    let categories = session
+        .find_elements("vehicle_category", &selectors.vehicle_category)
+        .await?;
+    let desired_category = purchase.vehicle_category.to_lowercase();
+    let mut is_selected = false;
+    for category in categories {
+        let category_text = category.inner_text().await?.unwrap_or_default();
+        if category_text.to_lowercase().contains(&desired_category) {
+            category.scroll_into_view().await?;
+            category.click().await?;
+            is_selected = true;
+            break;
+        }
+    }
+    if !is_selected {
+        return Err(anyhow!(
+            "Failed to find vehicle category {}",
+            purchase.vehicle_category
+        ));
+    }
+
+    fill_input(
+        session,
+        "plate_number",
+        &selectors.plate_number,
+        &purchase.plate_number,
+    )
+    .await
+}
+
+pub(super) async fn select_passengers(
+    session: &mut BookingSession<'_>,
+    purchase: &PurchaseConfig,
+) -> Result<()> {
+    let selectors = session.selectors;
+    for _ in 0..purchase.adults {
+        session
+            .require_element("adult_increment", &selectors.adult_increment)
+            .await?
+            .click()
+            .await?;
+    }
+    for _ in 0..purchase.children {
+        session
+            .require_element("child_increment", &selectors.child_increment)
+            .await?
+            .click()
+            .await?;
+    }
+    Ok(())
+}
+
+pub(super) async fn fill_contact(
+    session: &mut BookingSession<'_>,
+    purchase: &PurchaseConfig,
+) -> Result<()> {
+    let selectors = session.selectors;
+    fill_input(
+        session,
+        "contact_email",
+        &selectors.contact_email,
+        &purchase.email,
+    )
+    .await
+}
+
+pub async fn purchase_ticket(
+    event: &Event,
+    direction: &Direction,
+    date: &NaiveDate,
+    selectors: &SelectorProfile,
+    options: &BookingOptions,
+    purchase: &PurchaseConfig,
+    on_progress: &OnProgress<'_>,
+) -> Result<BookingOutcome> {
+    // The payment is left to the user, so the browser always needs a window
+    let options = BookingOptions {
+        headless: false,
+        ..options.clone()
+    };
+    let (mut browser, mut handle) = launch_browser(&options).await?;
+
+    let target = BookingTarget {
+        event: Some(event),
+        direction,
+        date,
+        purchase: Some(purchase),
+    };
+    let outcome = match open_session(&browser, &purchase_url(), selectors, &options).await {
+        Ok(mut session) => run_plan(&mut session, &PURCHASE_PLAN, &target, on_progress, true)
+            .await
+            .ok_or_else(|| anyhow!("Ticket purchase finished without an outcome")),
+        Err(err) => Err(err),
+    };
+
+    if let Ok(BookingOutcome::ReadyForPayment) = outcome {
+        info!("Ticket is ready for payment, waiting for the browser to be closed");
+        select! {
+            _ = &mut handle => return outcome,
+            _ = options.cancel.cancelled() => {}
+        }
+    }
+    close_browser(&mut browser, handle).await;
+    outcome
+}
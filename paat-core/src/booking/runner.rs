@@ -0,0 +1,150 @@
+use super::{
+    browser::{
+        open_booking_change, press_continue, read_confirmation, select_desired_direction,
+        select_right_booking_type, BookingSession, RowSelection,
+    },
+    date_picker::select_desired_date,
+    flow::{BookingProgress, OnProgress},
+    options::PurchaseConfig,
+    outcome::BookingOutcome,
+    purchase::{fill_contact, select_passengers, select_vehicle},
+    steps::BookingStep,
+};
+use crate::types::{event::Event, Direction};
+use anyhow::{anyhow, Result};
+use chrono::NaiveDate;
+use log::warn;
+use std::time::Duration;
+use tokio::{select, time::sleep};
+
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+pub(super) const CHANGE_PLAN: [BookingStep; 6] = [
+    BookingStep::OpenChange,
+    BookingStep::DatePicker,
+    BookingStep::DirectionPicker,
+    BookingStep::EventRows,
+    BookingStep::ContinueButton,
+    BookingStep::Confirmation,
+];
+
+pub(super) const DOCTOR_PLAN: [BookingStep; 5] = [
+    BookingStep::OpenChange,
+    BookingStep::DatePicker,
+    BookingStep::DirectionPicker,
+    BookingStep::EventRows,
+    BookingStep::ContinueButton,
+];
+
+pub(super) const PURCHASE_PLAN: [BookingStep; 7] = [
+    BookingStep::DirectionPicker,
+    BookingStep::DatePicker,
+    BookingStep::EventRows,
+    BookingStep::Vehicle,
+    BookingStep::Passengers,
+    BookingStep::ContinueButton,
+    BookingStep::Contact,
+];
+
+pub(super) struct BookingTarget<'a> {
+    pub event: Option<&'a Event>,
+    pub direction: &'a Direction,
+    pub date: &'a NaiveDate,
+    pub purchase: Option<&'a PurchaseConfig>,
+}
+
+impl<'a> BookingTarget<'a> {
+    fn purchase(&self) -> Result<&'a PurchaseConfig> {
+        self.purchase
+            .ok_or_else(|| anyhow!("No ticket details configured for the purchase"))
+    }
+}
+
+enum Transition {
+    Next,
+    Finish(BookingOutcome),
+}
+
+async fn run_step(
+    session: &mut BookingSession<'_>,
+    step: BookingStep,
+    target: &BookingTarget<'_>,
+) -> Result<Transition> {
+    match step {
+        BookingStep::OpenChange => open_booking_change(session).await?,
+        BookingStep::DatePicker => select_desired_date(session, target.date).await?,
+        BookingStep::DirectionPicker => select_desired_direction(session, target.direction).await?,
+        BookingStep::EventRows => match select_right_booking_type(session, target.event).await? {
+            RowSelection::Selected => {}
+            RowSelection::NotFound => return Ok(Transition::Finish(BookingOutcome::NoMatchingRow)),
+            RowSelection::SoldOut => {
+                return Ok(Transition::Finish(BookingOutcome::SpotGoneBeforeConfirm))
+            }
+        },
+        BookingStep::Vehicle => select_vehicle(session, target.purchase()?).await?,
+        BookingStep::Passengers => select_passengers(session, target.purchase()?).await?,
+        BookingStep::ContinueButton => {
+            press_continue(session).await?;
+            if session.options.dry_run {
+                return Ok(Transition::Finish(BookingOutcome::DryRun));
+            }
+        }
+        BookingStep::Confirmation => {
+            let event = target
+                .event
+                .ok_or_else(|| anyhow!("No sailing to confirm the booking against"))?;
+            let new_departure = read_confirmation(session, event, target.date).await?;
+            return Ok(Transition::Finish(BookingOutcome::Changed {
+                new_departure,
+            }));
+        }
+        BookingStep::Contact => {
+            fill_contact(session, target.purchase()?).await?;
+            return Ok(Transition::Finish(BookingOutcome::ReadyForPayment));
+        }
+    }
+    Ok(Transition::Next)
+}
+
+pub(super) async fn run_plan(
+    session: &mut BookingSession<'_>,
+    plan: &[BookingStep],
+    target: &BookingTarget<'_>,
+    on_progress: &OnProgress<'_>,
+    stop_on_error: bool,
+) -> Option<BookingOutcome> {
+    for (index, step) in plan.iter().copied().enumerate() {
+        let mut attempt = 1;
+        loop {
+            if session.options.cancel.is_cancelled() {
+                return Some(BookingOutcome::Cancelled);
+            }
+            on_progress(&BookingProgress {
+                step,
+                position: index + 1,
+                total: plan.len(),
+                attempt,
+            });
+            session.begin(step);
+            let result = run_step(session, step, target).await;
+            match session.finish(result).await {
+                Ok(Transition::Next) => break,
+                Ok(Transition::Finish(outcome)) => return Some(outcome),
+                Err(_) if session.options.cancel.is_cancelled() => {
+                    return Some(BookingOutcome::Cancelled)
+                }
+                Err(err) if step.allows_retry(attempt, session.options.step_retries) => {
+                    warn!("Booking step {} failed, retrying: {}", step, err);
+                    attempt += 1;
+                    select! {
+                        _ = sleep(RETRY_DELAY) => {}
+                        _ = session.options.cancel.cancelled() => {}
+                    }
+                }
+                Err(err) if stop_on_error => return Some(session.failed_outcome(err)),
+                Err(_) => break,
+            }
+        }
+    }
+    None
+}
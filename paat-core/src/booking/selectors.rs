@@ -0,0 +1,96 @@
+use crate::types::Direction;
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use std::{fs, path::Path};
+use toml::Value;
+
+pub const SELECTOR_PROFILE_VERSION: u32 = 1;
+const DEFAULT_SELECTOR_PROFILE: &str = include_str!("selectors.toml");
+const POSITION_PLACEHOLDER: &str = "{position}";
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct SelectorProfile {
+    pub version: u32,
+    pub change_button: Vec<String>,
+    pub direction_select: Vec<String>,
+    pub direction_option: Vec<String>,
+    pub date_select: Vec<String>,
+    pub selected_month: Vec<String>,
+    pub selected_year: Vec<String>,
+    pub next_month: Vec<String>,
+    pub previous_month: Vec<String>,
+    pub booking_date: Vec<String>,
+    pub booking_block: Vec<String>,
+    pub booking_row: Vec<String>,
+    pub departure_time: Vec<String>,
+    pub booking_button: Vec<String>,
+    pub continue_button: Vec<String>,
+    pub confirmed_departure: Vec<String>,
+    pub confirmed_date: Vec<String>,
+    pub vehicle_category: Vec<String>,
+    pub plate_number: Vec<String>,
+    pub adult_increment: Vec<String>,
+    pub child_increment: Vec<String>,
+    pub contact_email: Vec<String>,
+}
+
+impl Default for SelectorProfile {
+    fn default() -> Self {
+        toml::from_str(DEFAULT_SELECTOR_PROFILE).expect("Built-in selector profile is invalid")
+    }
+}
+
+impl SelectorProfile {
+    pub fn load<P: AsRef<Path>>(path: Option<P>) -> Result<Self> {
+        let path = match path {
+            Some(path) => path,
+            None => return Ok(Self::default()),
+        };
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read selector profile from {}", path.display()))?;
+        Self::from_overrides(&content)
+            .with_context(|| format!("Failed to parse selector profile from {}", path.display()))
+    }
+
+    pub fn from_overrides(content: &str) -> Result<Self> {
+        let overrides = toml::from_str::<Value>(content)?;
+        let overrides = overrides
+            .as_table()
+            .ok_or_else(|| anyhow!("Selector profile must be a table"))?;
+        let version = overrides
+            .get("version")
+            .and_then(Value::as_integer)
+            .unwrap_or(SELECTOR_PROFILE_VERSION as i64);
+        if version != SELECTOR_PROFILE_VERSION as i64 {
+            return Err(anyhow!(
+                "Selector profile version {} is not supported, expected {}",
+                version,
+                SELECTOR_PROFILE_VERSION
+            ));
+        }
+        let mut profile = toml::from_str::<Value>(DEFAULT_SELECTOR_PROFILE)?;
+        if let Some(table) = profile.as_table_mut() {
+            for (key, value) in overrides.iter() {
+                if !table.contains_key(key) {
+                    return Err(anyhow!("Unknown selector: {}", key));
+                }
+                table.insert(key.clone(), value.clone());
+            }
+        }
+        Ok(profile.try_into()?)
+    }
+
+    pub fn direction_option(&self, direction: &Direction) -> Vec<String> {
+        let position = match direction {
+            Direction::HR => 2,
+            Direction::RH => 3,
+            Direction::KV => 4,
+            Direction::VK => 5,
+        };
+        self.direction_option
+            .iter()
+            .map(|selector| selector.replace(POSITION_PLACEHOLDER, &position.to_string()))
+            .collect()
+    }
+}
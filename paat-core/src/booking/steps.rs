@@ -0,0 +1,187 @@
+use anyhow::Result;
+use std::time::{Duration, Instant};
+use strum_macros::Display;
+
+#[derive(Display, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookingStep {
+    #[strum(to_string = "open change")]
+    OpenChange,
+    #[strum(to_string = "date picker")]
+    DatePicker,
+    #[strum(to_string = "direction picker")]
+    DirectionPicker,
+    #[strum(to_string = "event rows")]
+    EventRows,
+    #[strum(to_string = "vehicle")]
+    Vehicle,
+    #[strum(to_string = "passengers")]
+    Passengers,
+    #[strum(to_string = "continue button")]
+    ContinueButton,
+    #[strum(to_string = "confirmation")]
+    Confirmation,
+    #[strum(to_string = "contact")]
+    Contact,
+}
+
+impl BookingStep {
+    pub fn action(&self) -> &'static str {
+        match self {
+            BookingStep::OpenChange => "opening the booking",
+            BookingStep::DatePicker => "choosing date",
+            BookingStep::DirectionPicker => "choosing direction",
+            BookingStep::EventRows => "choosing sailing",
+            BookingStep::Vehicle => "filling in vehicle",
+            BookingStep::Passengers => "adding passengers",
+            BookingStep::ContinueButton => "continuing",
+            BookingStep::Confirmation => "checking confirmation",
+            BookingStep::Contact => "filling in contact",
+        }
+    }
+
+    // Vehicle and passenger steps add to what a failed attempt already typed or
+    // clicked, and steps after continuing could confirm the change twice
+    pub fn is_retryable(&self) -> bool {
+        !matches!(
+            self,
+            BookingStep::Vehicle
+                | BookingStep::Passengers
+                | BookingStep::ContinueButton
+                | BookingStep::Confirmation
+                | BookingStep::Contact
+        )
+    }
+
+    pub fn allows_retry(&self, attempt: u32, step_retries: u32) -> bool {
+        self.is_retryable() && attempt <= step_retries
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SelectorMatch {
+    pub name: &'static str,
+    pub selector: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct StepReport {
+    pub step: BookingStep,
+    pub matched: Vec<SelectorMatch>,
+    pub failed: Vec<&'static str>,
+    pub duration: Duration,
+    pub error: Option<String>,
+}
+
+impl StepReport {
+    pub fn is_ok(&self) -> bool {
+        self.failed.is_empty() && self.error.is_none()
+    }
+}
+
+pub struct StepLog {
+    reports: Vec<StepReport>,
+    started_at: Instant,
+}
+
+impl Default for StepLog {
+    fn default() -> Self {
+        Self {
+            reports: Vec::new(),
+            started_at: Instant::now(),
+        }
+    }
+}
+
+impl StepLog {
+    pub fn begin(&mut self, step: BookingStep) {
+        self.started_at = Instant::now();
+        self.reports.push(StepReport {
+            step,
+            matched: Vec::new(),
+            failed: Vec::new(),
+            duration: Duration::ZERO,
+            error: None,
+        });
+    }
+
+    pub fn record(&mut self, name: &'static str, selector: Option<&str>) {
+        let report = match self.reports.last_mut() {
+            Some(report) => report,
+            None => return,
+        };
+        let is_matched = report.matched.iter().any(|matched| matched.name == name);
+        match selector {
+            Some(selector) => {
+                report.failed.retain(|failed| *failed != name);
+                if !is_matched {
+                    report.matched.push(SelectorMatch {
+                        name,
+                        selector: selector.to_string(),
+                    });
+                }
+            }
+            None => {
+                if !is_matched && !report.failed.contains(&name) {
+                    report.failed.push(name);
+                }
+            }
+        }
+    }
+
+    pub fn finish<T>(&mut self, result: Result<T>) -> Result<T> {
+        if let Some(report) = self.reports.last_mut() {
+            report.duration = self.started_at.elapsed();
+            if let Err(err) = &result {
+                report.error = Some(err.to_string());
+            }
+        }
+        result
+    }
+
+    #[cfg(feature = "booking")]
+    pub(crate) fn last(&self) -> Option<&StepReport> {
+        self.reports.last()
+    }
+
+    #[cfg(feature = "booking")]
+    pub(crate) fn len(&self) -> usize {
+        self.reports.len()
+    }
+
+    pub fn into_reports(self) -> Vec<StepReport> {
+        self.reports
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Runs a step that changes the form and then fails, as often as the retry policy allows
+    fn run_failing_step(step: BookingStep, form: &mut Vec<&'static str>, step_retries: u32) {
+        let mut attempt = 1;
+        loop {
+            form.push("2 adults");
+            if !step.allows_retry(attempt, step_retries) {
+                break;
+            }
+            attempt += 1;
+        }
+    }
+
+    #[test]
+    fn form_filling_steps_are_not_repeated() {
+        for step in [BookingStep::Vehicle, BookingStep::Passengers] {
+            let mut form = Vec::new();
+            run_failing_step(step, &mut form, 3);
+            assert_eq!(form, vec!["2 adults"], "{} was repeated", step);
+        }
+    }
+
+    #[test]
+    fn picker_steps_are_retried() {
+        let mut form = Vec::new();
+        run_failing_step(BookingStep::EventRows, &mut form, 2);
+        assert_eq!(form.len(), 3);
+    }
+}
@@ -0,0 +1,147 @@
+use crate::{
+    types::booking::{
+        Booking, BookingDetails, Ticket, TicketChangeEvent, TicketChangeRequest, TicketResponse,
+    },
+    url::{booking_api_url, ticket_api_url, tickets_api_url},
+};
+use anyhow::{anyhow, Result};
+use reqwest::{header::CONTENT_TYPE, Client as ReqwestClient, StatusCode};
+
+/// Result of changing a ticket through the API. `NotAccepted` means the change
+/// never reached the service, so it is safe to try another way. `Unverified`
+/// means the change was sent but its result is unknown.
+pub enum TicketChange {
+    Changed(Ticket),
+    NotAccepted(anyhow::Error),
+    Unverified(anyhow::Error),
+}
+
+#[derive(Clone)]
+pub struct BookingClient {
+    client: ReqwestClient,
+}
+
+impl Default for BookingClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BookingClient {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+        }
+    }
+
+    pub async fn fetch_booking(&self, booking_id: &str) -> Result<Booking> {
+        let response = self.client.get(booking_api_url(booking_id)).send().await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(anyhow!("Booking {} was not found", booking_id));
+        }
+        let body = response.error_for_status()?.text().await?;
+        serde_json::from_str::<Booking>(&body)
+            .map_err(|err| anyhow!("Failed to parse booking {}: {}", booking_id, err))
+    }
+
+    pub async fn fetch_tickets(&self, booking_id: &str) -> Result<Vec<Ticket>> {
+        let body = self
+            .client
+            .get(tickets_api_url(booking_id))
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        serde_json::from_str::<TicketResponse>(&body)
+            .map(|ticket_response| ticket_response.items)
+            .map_err(|err| anyhow!("Failed to parse tickets of {}: {}", booking_id, err))
+    }
+
+    pub async fn lookup_booking(&self, booking_id: &str) -> Result<BookingDetails> {
+        let booking = self.fetch_booking(booking_id).await?;
+        let tickets = if booking.tickets.is_empty() {
+            self.fetch_tickets(booking_id).await?
+        } else {
+            booking.tickets
+        };
+        let ticket = tickets
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("Booking {} has no tickets", booking_id))?;
+        BookingDetails::from_ticket(booking_id, ticket)
+    }
+
+    pub async fn change_ticket(
+        &self,
+        booking_id: &str,
+        ticket_id: &str,
+        event_uuid: &str,
+    ) -> TicketChange {
+        let body = match serde_json::to_string(&TicketChangeRequest {
+            event: TicketChangeEvent { uid: event_uuid },
+        }) {
+            Ok(body) => body,
+            Err(err) => return TicketChange::NotAccepted(err.into()),
+        };
+        let response = match self
+            .client
+            .put(ticket_api_url(booking_id, ticket_id))
+            .header(CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(err) if err.is_connect() => return TicketChange::NotAccepted(err.into()),
+            Err(err) => return TicketChange::Unverified(err.into()),
+        };
+        if response.status() == StatusCode::NOT_FOUND {
+            return TicketChange::NotAccepted(anyhow!(
+                "Changing tickets of booking {} is not supported",
+                booking_id
+            ));
+        }
+        if let Err(err) = response.error_for_status() {
+            return TicketChange::Unverified(err.into());
+        }
+
+        match self.fetch_tickets(booking_id).await {
+            Ok(tickets) => match tickets
+                .into_iter()
+                .find(|changed_ticket| changed_ticket.event.uuid == event_uuid)
+            {
+                Some(ticket) => TicketChange::Changed(ticket),
+                None => TicketChange::Unverified(anyhow!(
+                    "Booking {} is not listed on the new sailing",
+                    booking_id
+                )),
+            },
+            Err(err) => TicketChange::Unverified(err),
+        }
+    }
+
+    pub async fn change_booking(&self, booking_id: &str, event_uuid: &str) -> TicketChange {
+        let tickets = match self.fetch_tickets(booking_id).await {
+            Ok(tickets) => tickets,
+            Err(err) => return TicketChange::NotAccepted(err),
+        };
+        let ticket = match tickets.as_slice() {
+            [ticket] => ticket,
+            [] => {
+                return TicketChange::NotAccepted(anyhow!("Booking {} has no tickets", booking_id))
+            }
+            _ => {
+                return TicketChange::NotAccepted(anyhow!(
+                    "Booking {} has several tickets, choose one to change",
+                    booking_id
+                ))
+            }
+        };
+        if ticket.event.uuid == event_uuid {
+            return TicketChange::Changed(ticket.to_owned());
+        }
+        self.change_ticket(booking_id, &ticket.uuid, event_uuid)
+            .await
+    }
+}
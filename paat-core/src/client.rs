@@ -1,16 +1,21 @@
 use crate::{
-    datetime::naive_date_to_output_string,
+    datetime::{get_current_date, get_service_datetime, naive_date_to_output_string},
+    observer::Observer,
+    schedule::PollSchedule,
     types::{
-        event::{EventMap, EventResponse, WaitForSpot},
+        event::{Event, EventMap, EventResponse, WaitForSpot},
         Direction,
     },
     url::EVENTS_URL,
 };
 use anyhow::{anyhow, Result};
-use chrono::NaiveDate;
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
 use futures::{stream, Stream, StreamExt};
 use reqwest::Client as ReqwestClient;
-use std::time::Duration;
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
 use strum::EnumProperty;
 use tokio::time::sleep;
 
@@ -18,6 +23,35 @@ use tokio::time::sleep;
 pub struct Client {
     client: ReqwestClient,
     pause_between_stream_items: Duration,
+    observers: Vec<Arc<dyn Observer>>,
+    schedule: Option<Arc<dyn PollSchedule>>,
+    stop_before_departure: Duration,
+}
+
+#[derive(Clone, Copy)]
+enum SpotState {
+    NotFound,
+    Found,
+    Lost,
+}
+
+impl SpotState {
+    // Done is reported once when spots first show up, later polls only report changes
+    fn next(self, event: &Event) -> (Self, WaitForSpot) {
+        let has_spots = event.capacities.small_vehicles > 0;
+        match (self, has_spots) {
+            (SpotState::NotFound, true) => (SpotState::Found, WaitForSpot::Done(event.to_owned())),
+            (SpotState::Lost, true) => (SpotState::Found, WaitForSpot::Regained(event.to_owned())),
+            (SpotState::Found, false) => (SpotState::Lost, WaitForSpot::Lost(event.to_owned())),
+            (state, _) => (state, WaitForSpot::Waiting),
+        }
+    }
+}
+
+#[derive(Default)]
+struct PollState {
+    previous_failed: bool,
+    departure: Option<DateTime<FixedOffset>>,
 }
 
 impl Client {
@@ -25,6 +59,50 @@ impl Client {
         Self {
             client: reqwest::Client::new(),
             pause_between_stream_items,
+            observers: Vec::new(),
+            schedule: None,
+            stop_before_departure: Duration::ZERO,
+        }
+    }
+
+    pub fn with_observer(mut self, observer: Arc<dyn Observer>) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
+    pub fn with_schedule(mut self, schedule: Arc<dyn PollSchedule>) -> Self {
+        self.schedule = Some(schedule);
+        self
+    }
+
+    pub fn with_stop_before_departure(mut self, stop_before_departure: Duration) -> Self {
+        self.stop_before_departure = stop_before_departure;
+        self
+    }
+
+    fn has_expired(
+        &self,
+        departure_date: &NaiveDate,
+        departure: Option<&DateTime<FixedOffset>>,
+    ) -> bool {
+        let stop_before_departure = chrono::Duration::from_std(self.stop_before_departure)
+            .unwrap_or_else(|_| chrono::Duration::zero());
+        match departure {
+            Some(departure) => Utc::now() + stop_before_departure >= *departure,
+            None => *departure_date < get_current_date(),
+        }
+    }
+
+    pub fn pause_before_poll(
+        &self,
+        direction: &Direction,
+        departure: Option<&DateTime<FixedOffset>>,
+    ) -> Duration {
+        match (&self.schedule, departure) {
+            (Some(schedule), Some(departure)) => schedule
+                .pause(direction, departure)
+                .unwrap_or(self.pause_between_stream_items),
+            _ => self.pause_between_stream_items,
         }
     }
 
@@ -32,6 +110,19 @@ impl Client {
         &self,
         departure_date: &NaiveDate,
         direction: &Direction,
+    ) -> Result<EventMap> {
+        let started_at = Instant::now();
+        let result = self.request_events(departure_date, direction).await;
+        for observer in self.observers.iter() {
+            observer.on_request(direction, result.is_ok(), started_at.elapsed());
+        }
+        result
+    }
+
+    async fn request_events(
+        &self,
+        departure_date: &NaiveDate,
+        direction: &Direction,
     ) -> Result<EventMap> {
         let body = self
             .client
@@ -65,12 +156,36 @@ impl Client {
         &'a self,
         departure_date: &'a NaiveDate,
         direction: &'a Direction,
+        event_uuid: &'a str,
     ) -> impl Stream<Item = Result<EventMap>> + 'a {
-        stream::iter(0..).then(move |i| async move {
-            if i > 0 {
-                sleep(self.pause_between_stream_items).await;
+        let poll_state = Arc::new(Mutex::new(PollState::default()));
+        stream::iter(0..).then(move |i| {
+            let poll_state = poll_state.clone();
+            async move {
+                let (previous_failed, departure) = {
+                    let poll_state = poll_state.lock().unwrap();
+                    (poll_state.previous_failed, poll_state.departure)
+                };
+                if i > 0 {
+                    sleep(self.pause_before_poll(direction, departure.as_ref())).await;
+                }
+                if previous_failed {
+                    for observer in self.observers.iter() {
+                        observer.on_retry(departure_date, direction);
+                    }
+                }
+                let event_map_result = self.fetch_events(departure_date, direction).await;
+                let mut poll_state = poll_state.lock().unwrap();
+                poll_state.previous_failed = event_map_result.is_err();
+                let event_map = event_map_result?;
+                if let Some(event) = event_map.get(event_uuid) {
+                    poll_state.departure = get_service_datetime(&event.start).ok();
+                }
+                for observer in self.observers.iter() {
+                    observer.on_events(departure_date, direction, &event_map);
+                }
+                Ok(event_map)
             }
-            self.fetch_events(departure_date, direction).await
         })
     }
 
@@ -80,19 +195,170 @@ impl Client {
         direction: &'a Direction,
         event_uuid: &'a str,
     ) -> impl Stream<Item = Result<WaitForSpot>> + 'a {
-        self.create_event_stream(departure_date, direction)
+        self.create_spot_stream(departure_date, direction, event_uuid, false)
+    }
+
+    pub fn create_continuous_wait_stream<'a>(
+        &'a self,
+        departure_date: &'a NaiveDate,
+        direction: &'a Direction,
+        event_uuid: &'a str,
+    ) -> impl Stream<Item = Result<WaitForSpot>> + 'a {
+        self.create_spot_stream(departure_date, direction, event_uuid, true)
+    }
+
+    pub fn create_spot_stream<'a>(
+        &'a self,
+        departure_date: &'a NaiveDate,
+        direction: &'a Direction,
+        event_uuid: &'a str,
+        continuous: bool,
+    ) -> impl Stream<Item = Result<WaitForSpot>> + 'a {
+        let guard = WaitGuard::new(&self.observers, departure_date, direction, event_uuid);
+        let mut departure = None;
+        let mut spot_state = SpotState::NotFound;
+        let spot_stream = self
+            .create_event_stream(departure_date, direction, event_uuid)
             .map(move |event_map_result| {
+                let _ = &guard;
+                if let Some(event) = event_map_result
+                    .as_ref()
+                    .ok()
+                    .and_then(|event_map| event_map.get(event_uuid))
+                {
+                    departure = get_service_datetime(&event.start).ok();
+                }
+                if self.has_expired(departure_date, departure.as_ref()) {
+                    return Ok(WaitForSpot::Expired);
+                }
                 let event_map = event_map_result?;
                 if let Some(event) = event_map.get(event_uuid) {
-                    if event.capacities.small_vehicles > 0 {
-                        return Ok(WaitForSpot::Done(event.to_owned()));
+                    for observer in self.observers.iter() {
+                        observer.on_watched_event(departure_date, direction, event);
                     }
-                    return Ok(WaitForSpot::Waiting);
+                    if !continuous {
+                        if event.capacities.small_vehicles > 0 {
+                            return Ok(WaitForSpot::Done(event.to_owned()));
+                        }
+                        return Ok(WaitForSpot::Waiting);
+                    }
+                    let (next_state, wait_for_spot) = spot_state.next(event);
+                    spot_state = next_state;
+                    return Ok(wait_for_spot);
                 }
                 Err(anyhow!(
                     "Failed to find corresponding event with following uuid: {}",
                     event_uuid
                 ))
+            });
+        // The stream ends after reporting the departure, without polling again
+        stream::unfold(
+            (Box::pin(spot_stream), false),
+            |(mut spot_stream, expired)| async move {
+                if expired {
+                    return None;
+                }
+                let wait_result = spot_stream.next().await?;
+                let expired = matches!(wait_result, Ok(WaitForSpot::Expired));
+                Some((wait_result, (spot_stream, expired)))
+            },
+        )
+    }
+}
+
+struct WaitGuard<'a> {
+    observers: &'a [Arc<dyn Observer>],
+    departure_date: &'a NaiveDate,
+    direction: &'a Direction,
+    event_uuid: &'a str,
+}
+
+impl<'a> WaitGuard<'a> {
+    fn new(
+        observers: &'a [Arc<dyn Observer>],
+        departure_date: &'a NaiveDate,
+        direction: &'a Direction,
+        event_uuid: &'a str,
+    ) -> Self {
+        for observer in observers.iter() {
+            observer.on_wait_started(departure_date, direction, event_uuid);
+        }
+        Self {
+            observers,
+            departure_date,
+            direction,
+            event_uuid,
+        }
+    }
+}
+
+impl<'a> Drop for WaitGuard<'a> {
+    fn drop(&mut self) {
+        for observer in self.observers.iter() {
+            observer.on_wait_finished(self.departure_date, self.direction, self.event_uuid);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_event(small_vehicles: i32) -> Event {
+        let mut event: Event = serde_json::from_str(
+            r#"{
+                "uid": "e6b5e6d2",
+                "capacities": {"pcs": 120, "bc": 4, "sv": 0, "bv": 0, "dc": 1},
+                "pricelist": {"code": "VK"},
+                "transportationType": {"code": "REGULAR"},
+                "ship": {"code": "TIIU"},
+                "status": "IN_SALE",
+                "dtstart": "2026-07-03T17:30:00.000+0300",
+                "dtend": "2026-07-03T18:00:00.000+0300"
+            }"#,
+        )
+        .unwrap();
+        event.capacities.small_vehicles = small_vehicles;
+        event
+    }
+
+    fn run(small_vehicles: &[i32]) -> Vec<WaitForSpot> {
+        let mut spot_state = SpotState::NotFound;
+        small_vehicles
+            .iter()
+            .map(|small_vehicles| {
+                let (next_state, wait_for_spot) = spot_state.next(&create_event(*small_vehicles));
+                spot_state = next_state;
+                wait_for_spot
             })
+            .collect()
+    }
+
+    #[test]
+    fn reports_found_spots_once() {
+        assert_eq!(
+            run(&[0, 2, 2, 1]),
+            vec![
+                WaitForSpot::Waiting,
+                WaitForSpot::Done(create_event(2)),
+                WaitForSpot::Waiting,
+                WaitForSpot::Waiting,
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_lost_and_regained_spots_once() {
+        assert_eq!(
+            run(&[1, 0, 0, 3, 3, 0]),
+            vec![
+                WaitForSpot::Done(create_event(1)),
+                WaitForSpot::Lost(create_event(0)),
+                WaitForSpot::Waiting,
+                WaitForSpot::Regained(create_event(3)),
+                WaitForSpot::Waiting,
+                WaitForSpot::Lost(create_event(0)),
+            ]
+        );
     }
 }
@@ -0,0 +1,87 @@
+#[cfg(feature = "recorder")]
+use crate::analytics::ScheduleConfig;
+use crate::booking::BookingConfig;
+#[cfg(feature = "mqtt")]
+use crate::mqtt::MqttConfig;
+use crate::notification::NotifierConfig;
+#[cfg(feature = "recorder")]
+use crate::recorder::RecorderConfig;
+#[cfg(feature = "music")]
+use crate::sound::SoundConfig;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+const CONFIG_PATH_VARIABLE: &str = "PAAT_CONFIG";
+const CONFIG_FILE: &str = "paat/config.toml";
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct Config {
+    pub notifiers: Vec<NotifierConfig>,
+    pub stop_before_departure_minutes: u64,
+    pub continuous: bool,
+    pub booking: BookingConfig,
+    #[cfg(feature = "mqtt")]
+    pub mqtt: Option<MqttConfig>,
+    #[cfg(feature = "recorder")]
+    pub recorder: Option<RecorderConfig>,
+    #[cfg(feature = "recorder")]
+    pub schedule: Option<ScheduleConfig>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            #[cfg(feature = "music")]
+            notifiers: vec![NotifierConfig::Sound(SoundConfig::default())],
+            #[cfg(not(feature = "music"))]
+            notifiers: vec![NotifierConfig::Bell],
+            stop_before_departure_minutes: 0,
+            continuous: false,
+            booking: BookingConfig::default(),
+            #[cfg(feature = "mqtt")]
+            mqtt: None,
+            #[cfg(feature = "recorder")]
+            recorder: None,
+            #[cfg(feature = "recorder")]
+            schedule: None,
+        }
+    }
+}
+
+impl Config {
+    pub fn stop_before_departure(&self) -> Duration {
+        Duration::from_secs(self.stop_before_departure_minutes * 60)
+    }
+
+    pub fn load() -> Result<Self> {
+        if let Ok(config_path) = env::var(CONFIG_PATH_VARIABLE) {
+            return Self::from_file(config_path);
+        }
+        match Self::default_path() {
+            Some(config_path) if config_path.exists() => Self::from_file(config_path),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config from {}", path.display()))?;
+        toml::from_str(&content)
+            .with_context(|| format!("Failed to parse config from {}", path.display()))
+    }
+
+    fn default_path() -> Option<PathBuf> {
+        env::var("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|_| env::var("HOME").map(|home| PathBuf::from(home).join(".config")))
+            .ok()
+            .map(|config_directory| config_directory.join(CONFIG_FILE))
+    }
+}
@@ -1,4 +1,4 @@
-use chrono::{DateTime, Local, NaiveDate, ParseError, ParseResult};
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, ParseError, ParseResult};
 
 const INPUT_DATE_FORMAT: &str = "%d.%m.%Y";
 const OUTPUT_DATE_FORMAT: &str = "%Y-%m-%d";
@@ -17,8 +17,12 @@ pub fn get_naive_date_from_output_format(input: &str) -> Result<NaiveDate, Parse
     NaiveDate::parse_from_str(input, OUTPUT_DATE_FORMAT)
 }
 
+pub fn get_service_datetime(input: &str) -> ParseResult<DateTime<FixedOffset>> {
+    DateTime::parse_from_str(input, DATETIME_FORMAT)
+}
+
 pub fn get_local_datetime(input: &str) -> ParseResult<DateTime<Local>> {
-    let fixed_offset_datetime = DateTime::parse_from_str(input, DATETIME_FORMAT)?;
+    let fixed_offset_datetime = get_service_datetime(input)?;
     Ok(DateTime::from(fixed_offset_datetime))
 }
 
@@ -1,8 +1,22 @@
+#[cfg(feature = "recorder")]
+pub mod analytics;
 pub mod booking;
+pub mod booking_client;
 pub mod client;
+pub mod config;
 pub mod constants;
 pub mod datetime;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+#[cfg(feature = "mqtt")]
+pub mod mqtt;
+pub mod notification;
+pub mod observer;
+#[cfg(feature = "recorder")]
+pub mod recorder;
+pub mod schedule;
 #[cfg(feature = "music")]
 pub mod sound;
 pub mod types;
 mod url;
+pub mod watch;
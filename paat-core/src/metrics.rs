@@ -0,0 +1,217 @@
+use crate::{
+    datetime::{naive_date_to_output_string, service_datetime_to_local_time_string},
+    observer::Observer,
+    types::{
+        event::{Capacity, Event},
+        Direction,
+    },
+};
+use anyhow::Result;
+use chrono::NaiveDate;
+use prometheus::{
+    HistogramOpts, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry, TextEncoder,
+};
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+use strum::EnumProperty;
+
+pub struct Metrics {
+    registry: Registry,
+    requests: IntCounterVec,
+    request_duration: HistogramVec,
+    retries: IntCounterVec,
+    active_watches: IntGauge,
+    capacities: IntGaugeVec,
+    watched_sailings: Mutex<HashMap<WatchedSailing, SailingLabels>>,
+}
+
+type WatchedSailing = (&'static str, String, String);
+
+#[derive(Default)]
+struct SailingLabels {
+    watch_count: usize,
+    departure: Option<String>,
+}
+
+impl Metrics {
+    pub fn new() -> Result<Self> {
+        let registry = Registry::new();
+        let requests = IntCounterVec::new(
+            Opts::new("paat_requests_total", "Requests made to praamid.ee"),
+            &["direction", "status"],
+        )?;
+        let request_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "paat_request_duration_seconds",
+                "Latency of requests made to praamid.ee",
+            ),
+            &["direction", "status"],
+        )?;
+        let retries = IntCounterVec::new(
+            Opts::new("paat_retries_total", "Polls retried after a failed poll"),
+            &["direction"],
+        )?;
+        let active_watches = IntGauge::new("paat_active_watches", "Sailings currently watched")?;
+        let capacities = IntGaugeVec::new(
+            Opts::new("paat_sailing_capacity", "Free capacity of a sailing"),
+            &["direction", "date", "departure", "event_uuid", "capacity"],
+        )?;
+        registry.register(Box::new(requests.clone()))?;
+        registry.register(Box::new(request_duration.clone()))?;
+        registry.register(Box::new(retries.clone()))?;
+        registry.register(Box::new(active_watches.clone()))?;
+        registry.register(Box::new(capacities.clone()))?;
+
+        Ok(Self {
+            registry,
+            requests,
+            request_duration,
+            retries,
+            active_watches,
+            capacities,
+            watched_sailings: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn remove_capacities(&self, sailing: &WatchedSailing, departure: &str) {
+        let (direction, date, event_uuid) = sailing;
+        for (capacity, _) in Capacity::default().fields() {
+            let _ = self.capacities.remove_label_values(&[
+                direction,
+                date.as_str(),
+                departure,
+                event_uuid.as_str(),
+                capacity,
+            ]);
+        }
+    }
+
+    pub fn render(&self) -> Result<String> {
+        Ok(TextEncoder::new().encode_to_string(&self.registry.gather())?)
+    }
+}
+
+fn direction_label(direction: &Direction) -> &'static str {
+    direction.get_str("Abbreviation").unwrap_or_default()
+}
+
+fn watched_sailing(
+    departure_date: &NaiveDate,
+    direction: &Direction,
+    event_uuid: &str,
+) -> WatchedSailing {
+    (
+        direction_label(direction),
+        naive_date_to_output_string(departure_date),
+        event_uuid.to_string(),
+    )
+}
+
+impl Observer for Metrics {
+    fn on_watched_event(&self, departure_date: &NaiveDate, direction: &Direction, event: &Event) {
+        let sailing = watched_sailing(departure_date, direction, &event.uuid);
+        let departure = service_datetime_to_local_time_string(&event.start).unwrap_or_default();
+        {
+            let mut watched_sailings = self.watched_sailings.lock().unwrap();
+            let labels = watched_sailings.entry(sailing.clone()).or_default();
+            if let Some(previous_departure) = labels.departure.replace(departure.clone()) {
+                if previous_departure != departure {
+                    self.remove_capacities(&sailing, &previous_departure);
+                }
+            }
+        }
+        let (direction, date, event_uuid) = &sailing;
+        for (capacity, value) in event.capacities.fields() {
+            self.capacities
+                .with_label_values(&[
+                    direction,
+                    date.as_str(),
+                    departure.as_str(),
+                    event_uuid.as_str(),
+                    capacity,
+                ])
+                .set(value as i64);
+        }
+    }
+
+    fn on_request(&self, direction: &Direction, succeeded: bool, latency: Duration) {
+        let status = if succeeded { "ok" } else { "error" };
+        let labels = [direction_label(direction), status];
+        self.requests.with_label_values(&labels).inc();
+        self.request_duration
+            .with_label_values(&labels)
+            .observe(latency.as_secs_f64());
+    }
+
+    fn on_retry(&self, _departure_date: &NaiveDate, direction: &Direction) {
+        self.retries
+            .with_label_values(&[direction_label(direction)])
+            .inc();
+    }
+
+    fn on_wait_started(&self, departure_date: &NaiveDate, direction: &Direction, event_uuid: &str) {
+        self.active_watches.inc();
+        let sailing = watched_sailing(departure_date, direction, event_uuid);
+        let mut watched_sailings = self.watched_sailings.lock().unwrap();
+        watched_sailings.entry(sailing).or_default().watch_count += 1;
+    }
+
+    fn on_wait_finished(
+        &self,
+        departure_date: &NaiveDate,
+        direction: &Direction,
+        event_uuid: &str,
+    ) {
+        self.active_watches.dec();
+        let sailing = watched_sailing(departure_date, direction, event_uuid);
+        let mut watched_sailings = self.watched_sailings.lock().unwrap();
+        let labels = match watched_sailings.get_mut(&sailing) {
+            Some(labels) => labels,
+            None => return,
+        };
+        labels.watch_count = labels.watch_count.saturating_sub(1);
+        if labels.watch_count > 0 {
+            return;
+        }
+        if let Some(departure) = &labels.departure {
+            self.remove_capacities(&sailing, departure);
+        }
+        watched_sailings.remove(&sailing);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_event() -> Event {
+        serde_json::from_str(
+            r#"{
+                "uid": "e6b5e6d2",
+                "capacities": {"pcs": 120, "bc": 4, "sv": 2, "bv": 0, "dc": 1},
+                "pricelist": {"code": "VK"},
+                "transportationType": {"code": "REGULAR"},
+                "ship": {"code": "TIIU"},
+                "status": "IN_SALE",
+                "dtstart": "2026-07-03T17:30:00.000+0300",
+                "dtend": "2026-07-03T18:00:00.000+0300"
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn removes_capacities_when_last_watch_finishes() {
+        let metrics = Metrics::new().unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 7, 3).unwrap();
+        let event = create_event();
+        metrics.on_wait_started(&date, &Direction::VK, &event.uuid);
+        metrics.on_wait_started(&date, &Direction::VK, &event.uuid);
+        metrics.on_watched_event(&date, &Direction::VK, &event);
+        assert!(metrics.render().unwrap().contains("paat_sailing_capacity"));
+
+        metrics.on_wait_finished(&date, &Direction::VK, &event.uuid);
+        assert!(metrics.render().unwrap().contains("paat_sailing_capacity"));
+        metrics.on_wait_finished(&date, &Direction::VK, &event.uuid);
+        assert!(!metrics.render().unwrap().contains("paat_sailing_capacity"));
+    }
+}
@@ -0,0 +1,460 @@
+use crate::{
+    datetime::{naive_date_to_output_string, service_datetime_to_local_time_string},
+    observer::Observer,
+    types::{
+        event::{Capacity, Event},
+        Direction,
+    },
+};
+use anyhow::{anyhow, Result};
+use chrono::NaiveDate;
+use log::{debug, warn};
+use rumqttc::{AsyncClient, MqttOptions, QoS};
+use serde::Deserialize;
+use serde_json::json;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Mutex,
+    time::Duration,
+};
+use strum::EnumProperty;
+use tokio::runtime::Handle;
+
+const REQUEST_CHANNEL_CAPACITY: usize = 100;
+const KEEP_ALIVE_DURATION: Duration = Duration::from_secs(30);
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+const AVAILABLE_PAYLOAD: &str = "ON";
+const UNAVAILABLE_PAYLOAD: &str = "OFF";
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct MqttConfig {
+    pub host: String,
+    #[serde(default = "default_port")]
+    pub port: u16,
+    #[serde(default = "default_client_id")]
+    pub client_id: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    #[serde(default = "default_topic_prefix")]
+    pub topic_prefix: String,
+    #[serde(default = "default_discovery_prefix")]
+    pub discovery_prefix: String,
+}
+
+fn default_port() -> u16 {
+    1883
+}
+
+fn default_client_id() -> String {
+    String::from("paat")
+}
+
+fn default_topic_prefix() -> String {
+    String::from("paat")
+}
+
+fn default_discovery_prefix() -> String {
+    String::from("homeassistant")
+}
+
+pub struct MqttPublisher {
+    client: AsyncClient,
+    config: MqttConfig,
+    announced_events: Mutex<HashSet<String>>,
+    watch_counts: Mutex<HashMap<String, usize>>,
+}
+
+impl MqttPublisher {
+    pub fn new(config: MqttConfig) -> Result<Self> {
+        let runtime = Handle::try_current()
+            .map_err(|_| anyhow!("MQTT publisher needs to be created inside a Tokio runtime"))?;
+        let mut options = MqttOptions::new(&config.client_id, &config.host, config.port);
+        options.set_keep_alive(KEEP_ALIVE_DURATION);
+        if let (Some(username), Some(password)) = (&config.username, &config.password) {
+            options.set_credentials(username, password);
+        }
+        let (client, mut event_loop) = AsyncClient::new(options, REQUEST_CHANNEL_CAPACITY);
+        runtime.spawn(async move {
+            loop {
+                if let Err(err) = event_loop.poll().await {
+                    warn!("MQTT connection failed: {}", err);
+                    tokio::time::sleep(RECONNECT_DELAY).await;
+                }
+            }
+        });
+
+        Ok(Self {
+            client,
+            config,
+            announced_events: Mutex::new(HashSet::new()),
+            watch_counts: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn object_id(event_uuid: &str) -> String {
+        let event_id = event_uuid
+            .chars()
+            .map(|character| match character.is_ascii_alphanumeric() {
+                true => character,
+                false => '_',
+            })
+            .collect::<String>();
+        format!("paat_{}", event_id)
+    }
+
+    fn state_topic(&self, event_uuid: &str, name: &str) -> String {
+        format!(
+            "{}/{}/{}",
+            self.config.topic_prefix,
+            Self::object_id(event_uuid),
+            name
+        )
+    }
+
+    fn discovery_topic(&self, component: &str, event_uuid: &str, name: &str) -> String {
+        format!(
+            "{}/{}/{}_{}/config",
+            self.config.discovery_prefix,
+            component,
+            Self::object_id(event_uuid),
+            name
+        )
+    }
+
+    fn publish(&self, topic: String, payload: String) -> Result<()> {
+        self.client
+            .try_publish(topic, QoS::AtLeastOnce, true, payload)?;
+        Ok(())
+    }
+
+    fn discovery_messages(
+        &self,
+        departure_date: &NaiveDate,
+        direction: &Direction,
+        event: &Event,
+    ) -> Vec<(String, String)> {
+        let object_id = Self::object_id(&event.uuid);
+        let device_name = format!(
+            "Ferry {} {} {}",
+            direction.get_str("Abbreviation").unwrap_or_default(),
+            naive_date_to_output_string(departure_date),
+            service_datetime_to_local_time_string(&event.start).unwrap_or_default()
+        );
+        let device = json!({
+            "identifiers": [object_id],
+            "name": device_name,
+            "manufacturer": "paat",
+            "model": direction.to_string(),
+        });
+        let mut messages = event
+            .capacities
+            .fields()
+            .iter()
+            .map(|(field, _)| {
+                let payload = json!({
+                    "name": format!("{} {}", device_name, field.replace('_', " ")),
+                    "unique_id": format!("{}_{}", object_id, field),
+                    "state_topic": self.state_topic(&event.uuid, field),
+                    "unit_of_measurement": "spots",
+                    "state_class": "measurement",
+                    "device": device,
+                });
+                (
+                    self.discovery_topic("sensor", &event.uuid, field),
+                    payload.to_string(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let payload = json!({
+            "name": format!("{} spot available", device_name),
+            "unique_id": format!("{}_available", object_id),
+            "state_topic": self.state_topic(&event.uuid, "available"),
+            "payload_on": AVAILABLE_PAYLOAD,
+            "payload_off": UNAVAILABLE_PAYLOAD,
+            "device": device,
+        });
+        messages.push((
+            self.discovery_topic("binary_sensor", &event.uuid, "available"),
+            payload.to_string(),
+        ));
+        messages
+    }
+
+    fn capacity_messages(&self, event: &Event) -> Vec<(String, String)> {
+        let mut messages = event
+            .capacities
+            .fields()
+            .iter()
+            .map(|(field, value)| (self.state_topic(&event.uuid, field), value.to_string()))
+            .collect::<Vec<_>>();
+        let available = match event.capacities.small_vehicles > 0 {
+            true => AVAILABLE_PAYLOAD,
+            false => UNAVAILABLE_PAYLOAD,
+        };
+        messages.push((
+            self.state_topic(&event.uuid, "available"),
+            available.to_string(),
+        ));
+        messages
+    }
+
+    // Every retained topic of a sailing, an empty retained payload removes it from the broker
+    fn retained_topics(&self, event_uuid: &str) -> Vec<String> {
+        let mut topics = Vec::new();
+        for (field, _) in Capacity::default().fields() {
+            topics.push(self.discovery_topic("sensor", event_uuid, field));
+            topics.push(self.state_topic(event_uuid, field));
+        }
+        topics.push(self.discovery_topic("binary_sensor", event_uuid, "available"));
+        topics.push(self.state_topic(event_uuid, "available"));
+        topics
+    }
+
+    fn publish_all(&self, messages: Vec<(String, String)>) -> Result<()> {
+        for (topic, payload) in messages {
+            self.publish(topic, payload)?;
+        }
+        Ok(())
+    }
+}
+
+impl Observer for MqttPublisher {
+    fn on_watched_event(&self, departure_date: &NaiveDate, direction: &Direction, event: &Event) {
+        let is_announced = self.announced_events.lock().unwrap().contains(&event.uuid);
+        if !is_announced {
+            match self.publish_all(self.discovery_messages(departure_date, direction, event)) {
+                Ok(()) => {
+                    self.announced_events
+                        .lock()
+                        .unwrap()
+                        .insert(event.uuid.clone());
+                }
+                Err(err) => debug!("Failed to publish MQTT discovery: {}", err),
+            }
+        }
+        if let Err(err) = self.publish_all(self.capacity_messages(event)) {
+            debug!("Failed to publish MQTT capacities: {}", err);
+        }
+    }
+
+    fn on_wait_started(
+        &self,
+        _departure_date: &NaiveDate,
+        _direction: &Direction,
+        event_uuid: &str,
+    ) {
+        *self
+            .watch_counts
+            .lock()
+            .unwrap()
+            .entry(event_uuid.to_string())
+            .or_default() += 1;
+    }
+
+    fn on_wait_finished(
+        &self,
+        _departure_date: &NaiveDate,
+        _direction: &Direction,
+        event_uuid: &str,
+    ) {
+        {
+            let mut watch_counts = self.watch_counts.lock().unwrap();
+            if let Some(watch_count) = watch_counts.get_mut(event_uuid) {
+                *watch_count -= 1;
+                if *watch_count > 0 {
+                    return;
+                }
+            }
+            watch_counts.remove(event_uuid);
+        }
+        if !self.announced_events.lock().unwrap().remove(event_uuid) {
+            return;
+        }
+        let messages = self
+            .retained_topics(event_uuid)
+            .into_iter()
+            .map(|topic| (topic, String::new()))
+            .collect();
+        if let Err(err) = self.publish_all(messages) {
+            debug!("Failed to clear MQTT topics: {}", err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::{TcpListener, TcpStream},
+        sync::mpsc::{unbounded_channel, UnboundedReceiver},
+        time::timeout,
+    };
+
+    const RECEIVE_TIMEOUT: Duration = Duration::from_secs(5);
+
+    struct Published {
+        topic: String,
+        payload: String,
+        retain: bool,
+    }
+
+    fn create_event() -> Event {
+        serde_json::from_str(
+            r#"{
+                "uid": "e6b5e6d2-5d1c",
+                "capacities": {"pcs": 120, "bc": 4, "sv": 2, "bv": 0, "dc": 1},
+                "pricelist": {"code": "VK"},
+                "transportationType": {"code": "REGULAR"},
+                "ship": {"code": "TIIU"},
+                "status": "IN_SALE",
+                "dtstart": "2026-07-03T17:30:00.000+0300",
+                "dtend": "2026-07-03T18:00:00.000+0300"
+            }"#,
+        )
+        .unwrap()
+    }
+
+    async fn read_packet(stream: &mut TcpStream) -> Option<(u8, Vec<u8>)> {
+        let header = stream.read_u8().await.ok()?;
+        let mut length = 0;
+        let mut shift = 0;
+        loop {
+            let byte = stream.read_u8().await.ok()?;
+            length |= ((byte & 0x7f) as usize) << shift;
+            if byte & 0x80 == 0 {
+                break;
+            }
+            shift += 7;
+        }
+        let mut body = vec![0; length];
+        stream.read_exact(&mut body).await.ok()?;
+        Some((header, body))
+    }
+
+    // Minimal MQTT 3.1.1 broker that accepts one client and forwards its publishes
+    async fn start_broker() -> (u16, UnboundedReceiver<Published>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (sender, receiver) = unbounded_channel();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            while let Some((header, body)) = read_packet(&mut stream).await {
+                match header >> 4 {
+                    1 => stream.write_all(&[0x20, 0x02, 0x00, 0x00]).await.unwrap(),
+                    3 => {
+                        let topic_length = u16::from_be_bytes([body[0], body[1]]) as usize;
+                        let topic = String::from_utf8_lossy(&body[2..2 + topic_length]);
+                        let mut payload_start = 2 + topic_length;
+                        if (header >> 1) & 0x03 > 0 {
+                            let packet_id = &body[payload_start..payload_start + 2];
+                            stream
+                                .write_all(&[0x40, 0x02, packet_id[0], packet_id[1]])
+                                .await
+                                .unwrap();
+                            payload_start += 2;
+                        }
+                        let _ = sender.send(Published {
+                            topic: topic.to_string(),
+                            payload: String::from_utf8_lossy(&body[payload_start..]).to_string(),
+                            retain: header & 0x01 == 1,
+                        });
+                    }
+                    12 => stream.write_all(&[0xd0, 0x00]).await.unwrap(),
+                    _ => {}
+                }
+            }
+        });
+        (port, receiver)
+    }
+
+    async fn receive(
+        published: &mut UnboundedReceiver<Published>,
+        count: usize,
+    ) -> HashMap<String, String> {
+        let mut messages = HashMap::new();
+        for _ in 0..count {
+            let message = timeout(RECEIVE_TIMEOUT, published.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            assert!(message.retain, "{} was not retained", message.topic);
+            messages.insert(message.topic, message.payload);
+        }
+        messages
+    }
+
+    #[test]
+    fn fails_outside_runtime() {
+        let config = MqttConfig {
+            host: String::from("127.0.0.1"),
+            port: default_port(),
+            client_id: default_client_id(),
+            username: None,
+            password: None,
+            topic_prefix: default_topic_prefix(),
+            discovery_prefix: default_discovery_prefix(),
+        };
+        assert!(MqttPublisher::new(config).is_err());
+    }
+
+    #[tokio::test]
+    async fn announces_publishes_and_clears_watched_sailing() {
+        let (port, mut published) = start_broker().await;
+        let publisher = MqttPublisher::new(MqttConfig {
+            host: String::from("127.0.0.1"),
+            port,
+            client_id: default_client_id(),
+            username: None,
+            password: None,
+            topic_prefix: default_topic_prefix(),
+            discovery_prefix: default_discovery_prefix(),
+        })
+        .unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 7, 3).unwrap();
+        let event = create_event();
+        publisher.on_wait_started(&date, &Direction::VK, &event.uuid);
+        publisher.on_wait_started(&date, &Direction::VK, &event.uuid);
+        publisher.on_watched_event(&date, &Direction::VK, &event);
+
+        let messages = receive(&mut published, 12).await;
+        let sensor: serde_json::Value = serde_json::from_str(
+            &messages["homeassistant/sensor/paat_e6b5e6d2_5d1c_small_vehicles/config"],
+        )
+        .unwrap();
+        assert_eq!(
+            sensor["state_topic"],
+            "paat/paat_e6b5e6d2_5d1c/small_vehicles"
+        );
+        assert_eq!(sensor["unique_id"], "paat_e6b5e6d2_5d1c_small_vehicles");
+        assert!(sensor["device"]["name"]
+            .as_str()
+            .unwrap()
+            .starts_with("Ferry VK 2026-07-03"));
+        let available: serde_json::Value = serde_json::from_str(
+            &messages["homeassistant/binary_sensor/paat_e6b5e6d2_5d1c_available/config"],
+        )
+        .unwrap();
+        assert_eq!(
+            available["state_topic"],
+            "paat/paat_e6b5e6d2_5d1c/available"
+        );
+        assert_eq!(messages["paat/paat_e6b5e6d2_5d1c/small_vehicles"], "2");
+        assert_eq!(messages["paat/paat_e6b5e6d2_5d1c/passengers"], "120");
+        assert_eq!(messages["paat/paat_e6b5e6d2_5d1c/available"], "ON");
+
+        // The sailing is still watched by the second watch
+        publisher.on_wait_finished(&date, &Direction::VK, &event.uuid);
+        assert!(timeout(Duration::from_millis(200), published.recv())
+            .await
+            .is_err());
+
+        publisher.on_wait_finished(&date, &Direction::VK, &event.uuid);
+        let cleared = receive(&mut published, 12).await;
+        assert!(cleared.values().all(String::is_empty));
+        assert_eq!(
+            cleared.keys().collect::<HashSet<_>>(),
+            messages.keys().collect::<HashSet<_>>()
+        );
+    }
+}
@@ -0,0 +1,39 @@
+use std::sync::{Arc, Mutex};
+use tokio::sync::oneshot::{self, Receiver, Sender};
+
+#[derive(Default)]
+struct AlarmState {
+    generation: usize,
+    sender: Option<Sender<()>>,
+}
+
+#[derive(Clone, Default)]
+pub struct Alarm {
+    state: Arc<Mutex<AlarmState>>,
+}
+
+impl Alarm {
+    pub fn arm(&self) -> Option<(usize, Receiver<()>)> {
+        let mut state = self.state.lock().unwrap();
+        if state.sender.is_some() {
+            return None;
+        }
+        let (sender, receiver) = oneshot::channel::<()>();
+        state.generation += 1;
+        state.sender = Some(sender);
+        Some((state.generation, receiver))
+    }
+
+    pub fn finish(&self, generation: usize) {
+        let mut state = self.state.lock().unwrap();
+        if state.generation == generation {
+            state.sender = None;
+        }
+    }
+
+    pub fn stop(&self) {
+        if let Some(sender) = self.state.lock().unwrap().sender.take() {
+            let _ = sender.send(());
+        }
+    }
+}
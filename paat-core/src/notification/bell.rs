@@ -0,0 +1,24 @@
+use super::{Notification, Notifier};
+use anyhow::Result;
+use futures::future::{BoxFuture, FutureExt};
+use std::io::{self, stdout, Write};
+
+const BELL: &str = "\x07";
+
+pub fn ring_bell() -> io::Result<()> {
+    let mut stdout = stdout();
+    stdout.write_all(BELL.as_bytes())?;
+    stdout.flush()
+}
+
+pub struct BellNotifier;
+
+impl Notifier for BellNotifier {
+    fn notify<'a>(&'a self, _: &'a Notification) -> BoxFuture<'a, Result<()>> {
+        async move {
+            ring_bell()?;
+            Ok(())
+        }
+        .boxed()
+    }
+}
@@ -0,0 +1,76 @@
+use super::{alarm::Alarm, Notification, NotificationKind, Notifier};
+use anyhow::{anyhow, Result};
+use futures::future::{BoxFuture, FutureExt};
+use log::debug;
+use notify_rust::{Notification as DesktopNotification, Timeout};
+use std::{process::Command, thread};
+use tokio::task::spawn_blocking;
+
+const APPLICATION_NAME: &str = "paat";
+const OPEN_BOOKING_ACTION: &str = "open-booking";
+const DISMISS_ALARM_ACTION: &str = "dismiss-alarm";
+const OPEN_COMMAND: &str = "xdg-open";
+// Expiring notifications also end the thread that waits for their actions
+const ACTION_TIMEOUT: Timeout = Timeout::Milliseconds(10 * 60 * 1000);
+
+pub struct DesktopNotifier {
+    alarm: Alarm,
+}
+
+impl DesktopNotifier {
+    pub fn new(alarm: Alarm) -> Self {
+        Self { alarm }
+    }
+
+    fn create_notification(notification: &Notification) -> DesktopNotification {
+        let mut desktop_notification = DesktopNotification::new();
+        desktop_notification
+            .appname(APPLICATION_NAME)
+            .summary(&notification.to_string())
+            .body(&format!(
+                "{} {}",
+                notification.direction, notification.event
+            ));
+        if notification.kind != NotificationKind::SpotLost {
+            desktop_notification
+                .action(OPEN_BOOKING_ACTION, "Open booking")
+                .action(DISMISS_ALARM_ACTION, "Dismiss alarm")
+                .timeout(ACTION_TIMEOUT);
+        }
+        desktop_notification
+    }
+}
+
+impl Notifier for DesktopNotifier {
+    fn notify<'a>(&'a self, notification: &'a Notification) -> BoxFuture<'a, Result<()>> {
+        async move {
+            let desktop_notification = Self::create_notification(notification);
+            let handle = spawn_blocking(move || desktop_notification.show())
+                .await?
+                .map_err(|err| anyhow!("Desktop notifications are not available: {}", err))?;
+
+            let alarm = self.alarm.clone();
+            let booking_url = notification
+                .details()
+                .into_iter()
+                .find(|(key, _)| *key == "booking_url")
+                .map(|(_, value)| value)
+                .unwrap_or_default();
+            // A detached thread, since the runtime would wait for a blocking task on shutdown.
+            // It ends once an action is picked or the notification expires.
+            thread::spawn(move || {
+                handle.wait_for_action(|action| match action {
+                    OPEN_BOOKING_ACTION => {
+                        if let Err(err) = Command::new(OPEN_COMMAND).arg(&booking_url).spawn() {
+                            debug!("Failed to open booking page: {}", err);
+                        }
+                    }
+                    DISMISS_ALARM_ACTION => alarm.stop(),
+                    _ => {}
+                })
+            });
+            Ok(())
+        }
+        .boxed()
+    }
+}
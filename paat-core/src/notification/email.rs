@@ -0,0 +1,274 @@
+use super::{
+    template::{escape_none, render},
+    Notification, Notifier,
+};
+use anyhow::{anyhow, Result};
+use futures::future::{BoxFuture, FutureExt};
+use lettre::{
+    message::{header::ContentType, Mailbox},
+    transport::smtp::authentication::Credentials,
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+};
+use serde::Deserialize;
+
+const DEFAULT_SUBJECT: &str = "Paat: {{summary}}";
+const DEFAULT_BODY: &str = "{{summary}}
+
+Line: {{line}}
+Date: {{date}}
+Departure: {{departure}}
+Arrival: {{arrival}}
+Ship: {{ship}}
+Small vehicles: {{small_vehicles}}
+Large vehicles: {{large_vehicles}}
+Passengers: {{passengers}}
+
+{{booking_url}}
+";
+
+#[derive(Deserialize, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum SmtpSecurity {
+    #[default]
+    Starttls,
+    Tls,
+    Plain,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct EmailConfig {
+    pub host: String,
+    pub port: Option<u16>,
+    #[serde(default)]
+    pub security: SmtpSecurity,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from: String,
+    pub to: Vec<String>,
+    #[serde(default = "default_subject")]
+    pub subject: String,
+    #[serde(default = "default_body")]
+    pub body: String,
+}
+
+fn default_subject() -> String {
+    DEFAULT_SUBJECT.to_string()
+}
+
+fn default_body() -> String {
+    DEFAULT_BODY.to_string()
+}
+
+pub struct EmailNotifier {
+    config: EmailConfig,
+}
+
+impl EmailNotifier {
+    pub fn new(config: EmailConfig) -> Self {
+        Self { config }
+    }
+
+    fn create_transport(&self) -> Result<AsyncSmtpTransport<Tokio1Executor>> {
+        let mut builder = match self.config.security {
+            SmtpSecurity::Starttls => {
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&self.config.host)?
+            }
+            SmtpSecurity::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&self.config.host)?,
+            SmtpSecurity::Plain => {
+                AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&self.config.host)
+            }
+        };
+        if let Some(port) = self.config.port {
+            builder = builder.port(port);
+        }
+        if let (Some(username), Some(password)) = (&self.config.username, &self.config.password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+        Ok(builder.build())
+    }
+
+    fn create_message(&self, notification: &Notification) -> Result<Message> {
+        if self.config.to.is_empty() {
+            return Err(anyhow!("Email notifier has no recipients"));
+        }
+        let mut builder = Message::builder()
+            .from(self.config.from.parse::<Mailbox>()?)
+            .subject(render(&self.config.subject, notification, escape_none))
+            .header(ContentType::TEXT_PLAIN);
+        for recipient in self.config.to.iter() {
+            builder = builder.to(recipient.parse::<Mailbox>()?);
+        }
+        Ok(builder.body(render(&self.config.body, notification, escape_none))?)
+    }
+}
+
+impl Notifier for EmailNotifier {
+    fn notify<'a>(&'a self, notification: &'a Notification) -> BoxFuture<'a, Result<()>> {
+        async move {
+            let message = self.create_message(notification)?;
+            self.create_transport()?.send(message).await?;
+            Ok(())
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::test_notification;
+    use std::sync::{Arc, Mutex};
+    use tokio::{
+        io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+        net::TcpListener,
+    };
+
+    #[derive(Default)]
+    struct ReceivedMail {
+        recipients: Vec<String>,
+        data: String,
+    }
+
+    // Accepts a single SMTP session and records the envelope and message
+    async fn start_sink() -> (u16, Arc<Mutex<ReceivedMail>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let mail = Arc::new(Mutex::new(ReceivedMail::default()));
+        let received = mail.clone();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (reader, mut writer) = stream.into_split();
+            let mut lines = BufReader::new(reader).lines();
+            writer.write_all(b"220 localhost ESMTP\r\n").await.unwrap();
+            let mut in_data = false;
+            while let Some(line) = lines.next_line().await.unwrap() {
+                if in_data {
+                    if line == "." {
+                        in_data = false;
+                        writer.write_all(b"250 Queued\r\n").await.unwrap();
+                    } else {
+                        let mut mail = received.lock().unwrap();
+                        mail.data.push_str(&line);
+                        mail.data.push('\n');
+                    }
+                    continue;
+                }
+                let command = line.to_uppercase();
+                let reply: &[u8] = if command.starts_with("EHLO") {
+                    b"250 localhost\r\n"
+                } else if command.starts_with("RCPT TO:") {
+                    received
+                        .lock()
+                        .unwrap()
+                        .recipients
+                        .push(line[8..].to_string());
+                    b"250 OK\r\n"
+                } else if command.starts_with("DATA") {
+                    in_data = true;
+                    b"354 Go ahead\r\n"
+                } else if command.starts_with("QUIT") {
+                    writer.write_all(b"221 Bye\r\n").await.unwrap();
+                    break;
+                } else {
+                    b"250 OK\r\n"
+                };
+                writer.write_all(reply).await.unwrap();
+            }
+        });
+        (port, mail)
+    }
+
+    fn parse_config(config: &str) -> EmailConfig {
+        toml::from_str(config).unwrap()
+    }
+
+    #[test]
+    fn parses_security_and_recipients() {
+        let config = parse_config(
+            r#"
+            host = "smtp.example.com"
+            security = "tls"
+            from = "Paat <paat@example.com>"
+            to = ["me@example.com", "Family <family@example.com>"]
+            "#,
+        );
+        assert!(matches!(config.security, SmtpSecurity::Tls));
+        assert_eq!(config.to.len(), 2);
+
+        let message = EmailNotifier::new(config)
+            .create_message(&test_notification())
+            .unwrap();
+        let recipients = message.envelope().to();
+        assert_eq!(recipients[0].to_string(), "me@example.com");
+        assert_eq!(recipients[1].to_string(), "family@example.com");
+    }
+
+    #[test]
+    fn defaults_to_starttls() {
+        let config = parse_config(
+            r#"
+            host = "smtp.example.com"
+            from = "paat@example.com"
+            to = ["me@example.com"]
+            "#,
+        );
+        assert!(matches!(config.security, SmtpSecurity::Starttls));
+    }
+
+    #[test]
+    fn rejects_unknown_security() {
+        assert!(toml::from_str::<EmailConfig>(
+            r#"
+            host = "smtp.example.com"
+            security = "ssl"
+            from = "paat@example.com"
+            to = ["me@example.com"]
+            "#,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_and_missing_recipients() {
+        let notification = test_notification();
+        let mut config = parse_config(
+            r#"
+            host = "smtp.example.com"
+            from = "paat@example.com"
+            to = ["not an address"]
+            "#,
+        );
+        assert!(EmailNotifier::new(config.clone())
+            .create_message(&notification)
+            .is_err());
+        config.to.clear();
+        assert!(EmailNotifier::new(config)
+            .create_message(&notification)
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn sends_mail_to_local_sink() {
+        let (port, mail) = start_sink().await;
+        let notifier = EmailNotifier::new(parse_config(&format!(
+            r#"
+            host = "127.0.0.1"
+            port = {}
+            security = "plain"
+            from = "paat@example.com"
+            to = ["me@example.com", "family@example.com"]
+            subject = "Spot on {{{{line_code}}}} {{{{date}}}}"
+            "#,
+            port
+        )));
+        notifier.notify(&test_notification()).await.unwrap();
+
+        let mail = mail.lock().unwrap();
+        assert_eq!(
+            mail.recipients,
+            vec!["<me@example.com>", "<family@example.com>"]
+        );
+        assert!(mail.data.contains("Subject: Spot on VK 2026-07-03"));
+        assert!(mail.data.contains("Small vehicles: 2"));
+    }
+}
@@ -0,0 +1,65 @@
+use super::{Notification, Notifier};
+use anyhow::{anyhow, Result};
+use futures::future::{BoxFuture, FutureExt};
+use serde::Deserialize;
+use std::process::Stdio;
+use tokio::process::Command;
+
+const ENVIRONMENT_PREFIX: &str = "PAAT_";
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ExecConfig {
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+pub struct ExecNotifier {
+    config: ExecConfig,
+    silenced: bool,
+}
+
+impl ExecNotifier {
+    pub fn new(config: ExecConfig) -> Self {
+        Self {
+            config,
+            silenced: false,
+        }
+    }
+
+    pub fn with_silenced_output(mut self) -> Self {
+        self.silenced = true;
+        self
+    }
+}
+
+impl Notifier for ExecNotifier {
+    fn notify<'a>(&'a self, notification: &'a Notification) -> BoxFuture<'a, Result<()>> {
+        async move {
+            let environment = notification.details().into_iter().map(|(key, value)| {
+                (
+                    format!("{}{}", ENVIRONMENT_PREFIX, key.to_uppercase()),
+                    value,
+                )
+            });
+            let mut command = Command::new(&self.config.command);
+            command.args(&self.config.args).envs(environment);
+            if self.silenced {
+                command
+                    .stdin(Stdio::null())
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null());
+            }
+            let status = command.status().await?;
+            if !status.success() {
+                return Err(anyhow!(
+                    "Notification command {} exited with {}",
+                    self.config.command,
+                    status
+                ));
+            }
+            Ok(())
+        }
+        .boxed()
+    }
+}
@@ -0,0 +1,272 @@
+pub mod alarm;
+pub mod bell;
+#[cfg(all(feature = "desktop", target_os = "linux"))]
+pub mod desktop;
+#[cfg(feature = "email")]
+pub mod email;
+pub mod exec;
+#[cfg(feature = "music")]
+pub mod sound;
+pub mod stdout;
+pub mod template;
+pub mod webhook;
+
+use crate::{
+    datetime::{naive_date_to_output_string, service_datetime_to_local_time_string},
+    types::{event::Event, Direction},
+    url::{booking_url, PORTAL_URL},
+};
+use anyhow::{anyhow, Result};
+use chrono::NaiveDate;
+use futures::future::{join_all, BoxFuture};
+use log::warn;
+use serde::Deserialize;
+use std::fmt::{Display, Formatter};
+use strum::EnumProperty;
+use strum_macros::Display as StrumDisplay;
+
+#[derive(StrumDisplay, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    #[strum(to_string = "spot-found")]
+    SpotFound,
+    #[strum(to_string = "spot-lost")]
+    SpotLost,
+    #[strum(to_string = "spot-regained")]
+    SpotRegained,
+}
+
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub kind: NotificationKind,
+    pub event: Event,
+    pub direction: Direction,
+    pub departure_date: NaiveDate,
+    pub booking_id: Option<String>,
+}
+
+impl Notification {
+    pub fn new(
+        kind: NotificationKind,
+        event: Event,
+        direction: Direction,
+        departure_date: NaiveDate,
+    ) -> Self {
+        Self {
+            kind,
+            event,
+            direction,
+            departure_date,
+            booking_id: None,
+        }
+    }
+
+    pub fn with_booking_id(mut self, booking_id: Option<String>) -> Self {
+        self.booking_id = booking_id;
+        self
+    }
+
+    pub fn details(&self) -> Vec<(&'static str, String)> {
+        let capacities = &self.event.capacities;
+        vec![
+            ("kind", self.kind.to_string()),
+            ("summary", self.to_string()),
+            ("line", self.direction.to_string()),
+            (
+                "line_code",
+                self.direction
+                    .get_str("Abbreviation")
+                    .unwrap_or_default()
+                    .to_string(),
+            ),
+            ("date", naive_date_to_output_string(&self.departure_date)),
+            (
+                "departure",
+                service_datetime_to_local_time_string(&self.event.start).unwrap_or_default(),
+            ),
+            (
+                "arrival",
+                service_datetime_to_local_time_string(&self.event.end).unwrap_or_default(),
+            ),
+            ("ship", self.event.ship.code.clone()),
+            ("event_uuid", self.event.uuid.clone()),
+            ("passengers", capacities.passengers.to_string()),
+            ("small_vehicles", capacities.small_vehicles.to_string()),
+            ("large_vehicles", capacities.large_vehicles.to_string()),
+            ("bc", capacities.bc.to_string()),
+            ("dc", capacities.dc.to_string()),
+            (
+                "booking_url",
+                self.booking_id
+                    .as_deref()
+                    .map(booking_url)
+                    .unwrap_or_else(|| PORTAL_URL.to_string()),
+            ),
+        ]
+    }
+}
+
+impl Display for Notification {
+    fn fmt(&self, fmt: &mut Formatter) -> std::fmt::Result {
+        let summary = match self.kind {
+            NotificationKind::SpotFound => "Spot found",
+            NotificationKind::SpotLost => "Spot lost",
+            NotificationKind::SpotRegained => "Spot regained",
+        };
+        write!(
+            fmt,
+            "{}: {} {} {}, {} small vehicle spot(s)",
+            summary,
+            self.direction,
+            naive_date_to_output_string(&self.departure_date),
+            self.event,
+            self.event.capacities.small_vehicles
+        )
+    }
+}
+
+pub trait Notifier: Send + Sync {
+    fn notify<'a>(&'a self, notification: &'a Notification) -> BoxFuture<'a, Result<()>>;
+
+    fn dismiss(&self) {}
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum NotifierConfig {
+    #[cfg(feature = "music")]
+    Sound(crate::sound::SoundConfig),
+    Bell,
+    #[cfg(all(feature = "desktop", target_os = "linux"))]
+    Desktop,
+    Stdout,
+    Exec(exec::ExecConfig),
+    Webhook(webhook::WebhookConfig),
+    #[cfg(feature = "email")]
+    Email(email::EmailConfig),
+}
+
+#[derive(Default)]
+pub struct Dispatcher {
+    notifiers: Vec<Box<dyn Notifier>>,
+    alarm: alarm::Alarm,
+}
+
+impl Dispatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_config(configs: &[NotifierConfig]) -> Self {
+        Self::build(configs, true)
+    }
+
+    // Used when another program owns the terminal, so nothing may be printed to it
+    pub fn from_config_without_terminal(configs: &[NotifierConfig]) -> Self {
+        Self::build(configs, false)
+    }
+
+    fn build(configs: &[NotifierConfig], use_terminal: bool) -> Self {
+        configs
+            .iter()
+            .fold(Self::new(), |dispatcher, config| match config {
+                #[cfg(feature = "music")]
+                NotifierConfig::Sound(sound_config) => {
+                    let alarm = dispatcher.alarm.clone();
+                    dispatcher.with_notifier(sound::SoundNotifier::new(sound_config.clone(), alarm))
+                }
+                NotifierConfig::Bell => dispatcher.with_notifier(bell::BellNotifier),
+                #[cfg(all(feature = "desktop", target_os = "linux"))]
+                NotifierConfig::Desktop => {
+                    let alarm = dispatcher.alarm.clone();
+                    dispatcher.with_notifier(desktop::DesktopNotifier::new(alarm))
+                }
+                NotifierConfig::Stdout if use_terminal => {
+                    dispatcher.with_notifier(stdout::StdoutNotifier)
+                }
+                NotifierConfig::Stdout => dispatcher,
+                NotifierConfig::Exec(exec_config) if use_terminal => {
+                    dispatcher.with_notifier(exec::ExecNotifier::new(exec_config.clone()))
+                }
+                NotifierConfig::Exec(exec_config) => dispatcher.with_notifier(
+                    exec::ExecNotifier::new(exec_config.clone()).with_silenced_output(),
+                ),
+                NotifierConfig::Webhook(webhook_config) => {
+                    dispatcher.with_notifier(webhook::WebhookNotifier::new(webhook_config.clone()))
+                }
+                #[cfg(feature = "email")]
+                NotifierConfig::Email(email_config) => {
+                    dispatcher.with_notifier(email::EmailNotifier::new(email_config.clone()))
+                }
+            })
+    }
+
+    pub fn with_notifier<N>(mut self, notifier: N) -> Self
+    where
+        N: Notifier + 'static,
+    {
+        self.notifiers.push(Box::new(notifier));
+        self
+    }
+
+    pub async fn dispatch(&self, notification: &Notification) -> Result<()> {
+        if notification.kind == NotificationKind::SpotLost {
+            self.alarm.stop();
+        }
+        let results = join_all(
+            self.notifiers
+                .iter()
+                .map(|notifier| notifier.notify(notification)),
+        )
+        .await;
+        let errors = results
+            .into_iter()
+            .filter_map(|result| result.err())
+            .map(|error| {
+                warn!(
+                    "Failed to send {} notification: {}",
+                    notification.kind, error
+                );
+                error.to_string()
+            })
+            .collect::<Vec<String>>();
+        if errors.is_empty() {
+            return Ok(());
+        }
+
+        Err(anyhow!(
+            "Failed to send {} notification: {}",
+            notification.kind,
+            errors.join(", ")
+        ))
+    }
+
+    pub fn dismiss(&self) {
+        self.alarm.stop();
+        for notifier in self.notifiers.iter() {
+            notifier.dismiss();
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) fn test_notification() -> Notification {
+    let event = serde_json::from_str(
+        r#"{
+            "uid": "e6b5e6d2-5d1c-4c1e-9a7e-3f2b0c9d1a11",
+            "capacities": {"pcs": 120, "bc": 4, "sv": 2, "bv": 0, "dc": 1},
+            "pricelist": {"code": "VK"},
+            "transportationType": {"code": "REGULAR"},
+            "ship": {"code": "Tõll \"Express\"\n"},
+            "status": "IN_SALE",
+            "dtstart": "2026-07-03T17:30:00.000+0300",
+            "dtend": "2026-07-03T18:00:00.000+0300"
+        }"#,
+    )
+    .unwrap();
+    Notification::new(
+        NotificationKind::SpotFound,
+        event,
+        Direction::VK,
+        NaiveDate::from_ymd_opt(2026, 7, 3).unwrap(),
+    )
+}
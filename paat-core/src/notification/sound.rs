@@ -0,0 +1,55 @@
+use super::{alarm::Alarm, Notification, NotificationKind, Notifier};
+use crate::sound::{play_sound, DefaultSample, SoundConfig};
+use anyhow::Result;
+use futures::future::{BoxFuture, FutureExt};
+use log::debug;
+
+pub struct SoundNotifier {
+    alarm: Alarm,
+    config: SoundConfig,
+}
+
+impl SoundNotifier {
+    pub fn new(config: SoundConfig, alarm: Alarm) -> Self {
+        Self { alarm, config }
+    }
+}
+
+impl Notifier for SoundNotifier {
+    fn notify<'a>(&'a self, notification: &'a Notification) -> BoxFuture<'a, Result<()>> {
+        async move {
+            let (config, sound_file, default_sample) = match notification.kind {
+                NotificationKind::SpotFound | NotificationKind::SpotRegained => (
+                    self.config.clone(),
+                    self.config.spot_found_file.clone(),
+                    DefaultSample::SpotFound,
+                ),
+                NotificationKind::SpotLost => {
+                    let config = SoundConfig {
+                        repeat_count: Some(1),
+                        escalation: None,
+                        ..self.config.clone()
+                    };
+                    (
+                        config,
+                        self.config.spot_lost_file.clone(),
+                        DefaultSample::SpotLost,
+                    )
+                }
+            };
+            if let Some((generation, receiver)) = self.alarm.arm() {
+                let alarm = self.alarm.clone();
+                tokio::spawn(async move {
+                    if let Err(sound_error) =
+                        play_sound(&config, &sound_file, default_sample, receiver).await
+                    {
+                        debug!("Failed to play sound: {}", sound_error);
+                    }
+                    alarm.finish(generation);
+                });
+            }
+            Ok(())
+        }
+        .boxed()
+    }
+}
@@ -0,0 +1,15 @@
+use super::{Notification, Notifier};
+use anyhow::Result;
+use futures::future::{BoxFuture, FutureExt};
+
+pub struct StdoutNotifier;
+
+impl Notifier for StdoutNotifier {
+    fn notify<'a>(&'a self, notification: &'a Notification) -> BoxFuture<'a, Result<()>> {
+        async move {
+            println!("{}", notification);
+            Ok(())
+        }
+        .boxed()
+    }
+}
@@ -0,0 +1,55 @@
+use super::Notification;
+
+pub fn render<F>(template: &str, notification: &Notification, escape: F) -> String
+where
+    F: Fn(&str) -> String,
+{
+    notification
+        .details()
+        .into_iter()
+        .fold(template.to_string(), |rendered, (key, value)| {
+            rendered.replace(&format!("{{{{{}}}}}", key), &escape(&value))
+        })
+}
+
+pub fn escape_json(value: &str) -> String {
+    let quoted = serde_json::to_string(value).unwrap_or_default();
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+pub fn escape_none(value: &str) -> String {
+    value.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::test_notification;
+
+    #[test]
+    fn escapes_quotes_and_newlines_for_json() {
+        assert_eq!(escape_json("a \"b\"\nc"), "a \\\"b\\\"\\nc");
+    }
+
+    #[test]
+    fn renders_valid_json_from_values_that_need_escaping() {
+        let notification = test_notification();
+        let body = render(
+            r#"{"ship": "{{ship}}", "spots": {{small_vehicles}}}"#,
+            &notification,
+            escape_json,
+        );
+        let value = serde_json::from_str::<serde_json::Value>(&body).unwrap();
+        assert_eq!(value["ship"], "Tõll \"Express\"\n");
+        assert_eq!(value["spots"], 2);
+    }
+
+    #[test]
+    fn leaves_unknown_placeholders_untouched() {
+        let notification = test_notification();
+        assert_eq!(
+            render("{{line_code}} {{unknown}}", &notification, escape_none),
+            "VK {{unknown}}"
+        );
+    }
+}
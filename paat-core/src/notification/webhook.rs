@@ -0,0 +1,261 @@
+use super::{
+    template::{escape_json, render},
+    Notification, Notifier,
+};
+use anyhow::{anyhow, Result};
+use futures::future::{BoxFuture, FutureExt};
+use hmac::{Hmac, Mac};
+use log::debug;
+use reqwest::{header::CONTENT_TYPE, Client as ReqwestClient};
+use serde::Deserialize;
+use sha2::Sha256;
+use std::{collections::BTreeMap, time::Duration};
+use tokio::time::sleep;
+
+const DEFAULT_TEMPLATE: &str = r#"{"text": "{{summary}}"}"#;
+const DEFAULT_SIGNATURE_HEADER: &str = "X-Paat-Signature";
+const DEFAULT_RETRY_DELAY_MS: u64 = 1000;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct WebhookConfig {
+    pub url: String,
+    #[serde(default = "default_template")]
+    pub template: String,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default)]
+    pub retries: u32,
+    #[serde(default = "default_retry_delay_ms")]
+    pub retry_delay_ms: u64,
+    pub hmac_secret: Option<String>,
+    #[serde(default = "default_signature_header")]
+    pub signature_header: String,
+}
+
+fn default_template() -> String {
+    DEFAULT_TEMPLATE.to_string()
+}
+
+fn default_signature_header() -> String {
+    DEFAULT_SIGNATURE_HEADER.to_string()
+}
+
+fn default_retry_delay_ms() -> u64 {
+    DEFAULT_RETRY_DELAY_MS
+}
+
+pub struct WebhookNotifier {
+    client: ReqwestClient,
+    config: WebhookConfig,
+}
+
+impl WebhookNotifier {
+    pub fn new(config: WebhookConfig) -> Self {
+        Self {
+            client: ReqwestClient::new(),
+            config,
+        }
+    }
+
+    fn create_body(&self, notification: &Notification) -> Result<String> {
+        let body = render(&self.config.template, notification, escape_json);
+        serde_json::from_str::<serde_json::Value>(&body)
+            .map_err(|err| anyhow!("Webhook template did not render to valid JSON: {}", err))?;
+        Ok(body)
+    }
+
+    fn sign(&self, body: &str) -> Result<Option<String>> {
+        if let Some(secret) = &self.config.hmac_secret {
+            let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+                .map_err(|err| anyhow!("Invalid webhook HMAC secret: {}", err))?;
+            mac.update(body.as_bytes());
+            let signature = hex::encode(mac.finalize().into_bytes());
+            return Ok(Some(format!("sha256={}", signature)));
+        }
+        Ok(None)
+    }
+
+    async fn send(&self, body: &str, signature: &Option<String>) -> Result<()> {
+        let mut request = self
+            .client
+            .post(&self.config.url)
+            .header(CONTENT_TYPE, "application/json")
+            .body(body.to_string());
+        for (name, value) in self.config.headers.iter() {
+            request = request.header(name, value);
+        }
+        if let Some(signature) = signature {
+            request = request.header(&self.config.signature_header, signature);
+        }
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            return Err(anyhow!(
+                "Webhook {} responded with {}",
+                self.config.url,
+                response.status()
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Notifier for WebhookNotifier {
+    fn notify<'a>(&'a self, notification: &'a Notification) -> BoxFuture<'a, Result<()>> {
+        async move {
+            let body = self.create_body(notification)?;
+            let signature = self.sign(&body)?;
+            let mut attempt = 0;
+            loop {
+                match self.send(&body, &signature).await {
+                    Ok(()) => return Ok(()),
+                    Err(err) if attempt < self.config.retries => {
+                        attempt += 1;
+                        debug!(
+                            "Retrying webhook ({}/{}): {}",
+                            attempt, self.config.retries, err
+                        );
+                        sleep(Duration::from_millis(
+                            self.config.retry_delay_ms * attempt as u64,
+                        ))
+                        .await;
+                    }
+                    Err(err) => return Err(err),
+                }
+            }
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::test_notification;
+    use std::sync::{Arc, Mutex};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    struct ReceivedRequest {
+        headers: String,
+        body: String,
+    }
+
+    // Answers every request with the next status, repeating the last one
+    async fn start_listener(statuses: Vec<u16>) -> (String, Arc<Mutex<Vec<ReceivedRequest>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let received = requests.clone();
+        tokio::spawn(async move {
+            let mut index = 0;
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buffer = [0; 4096];
+                let (headers, body) = loop {
+                    let read = stream.read(&mut buffer).await.unwrap();
+                    request.extend_from_slice(&buffer[..read]);
+                    let text = String::from_utf8_lossy(&request).to_string();
+                    if let Some((headers, body)) = text.split_once("\r\n\r\n") {
+                        let content_length = headers
+                            .lines()
+                            .find_map(|line| {
+                                line.to_lowercase()
+                                    .strip_prefix("content-length: ")
+                                    .map(|length| length.parse::<usize>().unwrap())
+                            })
+                            .unwrap_or_default();
+                        if body.len() >= content_length {
+                            break (headers.to_string(), body.to_string());
+                        }
+                    }
+                };
+                received
+                    .lock()
+                    .unwrap()
+                    .push(ReceivedRequest { headers, body });
+                let status = statuses[index.min(statuses.len() - 1)];
+                index += 1;
+                let response = format!(
+                    "HTTP/1.1 {} Test\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+                    status
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        (url, requests)
+    }
+
+    fn create_config(url: String) -> WebhookConfig {
+        WebhookConfig {
+            url,
+            template: default_template(),
+            headers: BTreeMap::new(),
+            retries: 0,
+            retry_delay_ms: 1,
+            hmac_secret: None,
+            signature_header: default_signature_header(),
+        }
+    }
+
+    #[test]
+    fn signs_body_with_hmac_sha256() {
+        let notifier = WebhookNotifier::new(WebhookConfig {
+            hmac_secret: Some(String::from("key")),
+            ..create_config(String::new())
+        });
+        assert_eq!(
+            notifier
+                .sign("The quick brown fox jumps over the lazy dog")
+                .unwrap(),
+            Some(String::from(
+                "sha256=f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn posts_signed_json_to_listener() {
+        let (url, requests) = start_listener(vec![200]).await;
+        let notifier = WebhookNotifier::new(WebhookConfig {
+            hmac_secret: Some(String::from("secret")),
+            ..create_config(url)
+        });
+        let notification = test_notification();
+        notifier.notify(&notification).await.unwrap();
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        let request = &requests[0];
+        let body = serde_json::from_str::<serde_json::Value>(&request.body).unwrap();
+        assert_eq!(body["text"], notification.to_string());
+        let signature = notifier.sign(&request.body).unwrap().unwrap();
+        let headers = request.headers.to_lowercase();
+        assert!(headers.contains("content-type: application/json"));
+        assert!(headers.contains(&format!("x-paat-signature: {}", signature)));
+    }
+
+    #[tokio::test]
+    async fn retries_until_listener_accepts() {
+        let (url, requests) = start_listener(vec![500, 503, 200]).await;
+        let notifier = WebhookNotifier::new(WebhookConfig {
+            retries: 3,
+            ..create_config(url)
+        });
+        notifier.notify(&test_notification()).await.unwrap();
+        assert_eq!(requests.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_last_retry() {
+        let (url, requests) = start_listener(vec![500]).await;
+        let notifier = WebhookNotifier::new(WebhookConfig {
+            retries: 1,
+            ..create_config(url)
+        });
+        assert!(notifier.notify(&test_notification()).await.is_err());
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,38 @@
+use crate::types::{
+    event::{Event, EventMap},
+    Direction,
+};
+use chrono::NaiveDate;
+use std::time::Duration;
+
+pub trait Observer: Send + Sync {
+    fn on_events(&self, _departure_date: &NaiveDate, _direction: &Direction, _events: &EventMap) {}
+
+    fn on_watched_event(
+        &self,
+        _departure_date: &NaiveDate,
+        _direction: &Direction,
+        _event: &Event,
+    ) {
+    }
+
+    fn on_request(&self, _direction: &Direction, _succeeded: bool, _latency: Duration) {}
+
+    fn on_retry(&self, _departure_date: &NaiveDate, _direction: &Direction) {}
+
+    fn on_wait_started(
+        &self,
+        _departure_date: &NaiveDate,
+        _direction: &Direction,
+        _event_uuid: &str,
+    ) {
+    }
+
+    fn on_wait_finished(
+        &self,
+        _departure_date: &NaiveDate,
+        _direction: &Direction,
+        _event_uuid: &str,
+    ) {
+    }
+}
@@ -0,0 +1,180 @@
+use crate::{
+    datetime::naive_date_to_output_string,
+    observer::Observer,
+    types::{
+        event::{Capacity, Event, EventMap},
+        Direction,
+    },
+};
+use anyhow::{Context, Result};
+use chrono::{Local, NaiveDate};
+use log::warn;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Deserialize;
+use std::{path::PathBuf, sync::Mutex};
+use strum::EnumProperty;
+
+pub(crate) const CREATE_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS capacity_snapshots (
+        id INTEGER PRIMARY KEY,
+        recorded_at TEXT NOT NULL,
+        line TEXT NOT NULL,
+        departure_date TEXT NOT NULL,
+        event_uuid TEXT NOT NULL,
+        departure TEXT NOT NULL,
+        passengers INTEGER NOT NULL,
+        bc INTEGER NOT NULL,
+        small_vehicles INTEGER NOT NULL,
+        large_vehicles INTEGER NOT NULL,
+        dc INTEGER NOT NULL,
+        status TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS capacity_snapshots_event
+        ON capacity_snapshots (event_uuid, id);
+";
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct RecorderConfig {
+    pub path: PathBuf,
+}
+
+pub struct Recorder {
+    connection: Mutex<Connection>,
+}
+
+impl Recorder {
+    pub fn new(config: RecorderConfig) -> Result<Self> {
+        let connection = Connection::open(&config.path).with_context(|| {
+            format!(
+                "Failed to open capacity history from {}",
+                config.path.display()
+            )
+        })?;
+        connection.execute_batch(CREATE_TABLE)?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    fn is_unchanged(connection: &Connection, event: &Event) -> Result<bool> {
+        let last_snapshot = connection
+            .query_row(
+                "SELECT passengers, bc, small_vehicles, large_vehicles, dc, status
+                    FROM capacity_snapshots WHERE event_uuid = ?1 ORDER BY id DESC LIMIT 1",
+                params![event.uuid],
+                |row| {
+                    Ok((
+                        Capacity {
+                            passengers: row.get(0)?,
+                            bc: row.get(1)?,
+                            small_vehicles: row.get(2)?,
+                            large_vehicles: row.get(3)?,
+                            dc: row.get(4)?,
+                        },
+                        row.get::<_, String>(5)?,
+                    ))
+                },
+            )
+            .optional()?;
+        Ok(last_snapshot
+            .map(|(capacities, status)| capacities == event.capacities && status == event.status)
+            .unwrap_or(false))
+    }
+
+    pub fn record(
+        &self,
+        departure_date: &NaiveDate,
+        direction: &Direction,
+        event: &Event,
+    ) -> Result<()> {
+        let connection = self.connection.lock().unwrap();
+        if Self::is_unchanged(&connection, event)? {
+            return Ok(());
+        }
+        let capacities = &event.capacities;
+        connection.execute(
+            "INSERT INTO capacity_snapshots (recorded_at, line, departure_date, event_uuid,
+                departure, passengers, bc, small_vehicles, large_vehicles, dc, status)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                Local::now().to_rfc3339(),
+                direction.get_str("Abbreviation").unwrap_or_default(),
+                naive_date_to_output_string(departure_date),
+                event.uuid,
+                event.start,
+                capacities.passengers,
+                capacities.bc,
+                capacities.small_vehicles,
+                capacities.large_vehicles,
+                capacities.dc,
+                event.status,
+            ],
+        )?;
+        Ok(())
+    }
+}
+
+impl Observer for Recorder {
+    fn on_events(&self, departure_date: &NaiveDate, direction: &Direction, events: &EventMap) {
+        for event in events.values() {
+            if let Err(err) = self.record(departure_date, direction, event) {
+                warn!("Failed to record capacity snapshot: {}", err);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_event() -> Event {
+        serde_json::from_str(
+            r#"{
+                "uid": "e6b5e6d2",
+                "capacities": {"pcs": 120, "bc": 4, "sv": 2, "bv": 0, "dc": 1},
+                "pricelist": {"code": "VK"},
+                "transportationType": {"code": "REGULAR"},
+                "ship": {"code": "TIIU"},
+                "status": "IN_SALE",
+                "dtstart": "2026-07-03T17:30:00.000+0300",
+                "dtend": "2026-07-03T18:00:00.000+0300"
+            }"#,
+        )
+        .unwrap()
+    }
+
+    fn snapshot_count(recorder: &Recorder) -> i64 {
+        recorder
+            .connection
+            .lock()
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM capacity_snapshots", [], |row| {
+                row.get(0)
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn records_only_changed_snapshots() {
+        let recorder = Recorder::new(RecorderConfig {
+            path: PathBuf::from(":memory:"),
+        })
+        .unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 7, 3).unwrap();
+        let mut event = create_event();
+
+        recorder.record(&date, &Direction::VK, &event).unwrap();
+        recorder.record(&date, &Direction::VK, &event).unwrap();
+        assert_eq!(snapshot_count(&recorder), 1);
+
+        event.capacities.small_vehicles = 0;
+        recorder.record(&date, &Direction::VK, &event).unwrap();
+        recorder.record(&date, &Direction::VK, &event).unwrap();
+        assert_eq!(snapshot_count(&recorder), 2);
+
+        event.status = String::from("SOLD_OUT");
+        recorder.record(&date, &Direction::VK, &event).unwrap();
+        assert_eq!(snapshot_count(&recorder), 3);
+    }
+}
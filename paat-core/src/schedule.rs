@@ -0,0 +1,7 @@
+use crate::types::Direction;
+use chrono::{DateTime, FixedOffset};
+use std::time::Duration;
+
+pub trait PollSchedule: Send + Sync {
+    fn pause(&self, direction: &Direction, departure: &DateTime<FixedOffset>) -> Option<Duration>;
+}
@@ -1,24 +1,191 @@
+use crate::notification::bell::ring_bell;
 use anyhow::{Ok, Result};
+use log::debug;
 use rodio::{Decoder, OutputStream, Sink, Source};
 use send_wrapper::SendWrapper;
-use std::{include_bytes, io::Cursor, rc::Rc};
-use tokio::sync::oneshot::Receiver;
+use serde::Deserialize;
+use std::{
+    fs,
+    io::Cursor,
+    path::PathBuf,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+use tokio::{sync::oneshot::Receiver, time::interval};
+
+const SPOT_FOUND_SAMPLE: &[u8] = include_bytes!("../assets/sample.wav");
+const SPOT_LOST_SAMPLE: &[u8] = include_bytes!("../assets/spot-lost.wav");
+const SOUND_POLL_INTERVAL: Duration = Duration::from_millis(100);
+const BELL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DefaultSample {
+    SpotFound,
+    SpotLost,
+}
+
+impl DefaultSample {
+    fn bytes(&self) -> &'static [u8] {
+        match self {
+            DefaultSample::SpotFound => SPOT_FOUND_SAMPLE,
+            DefaultSample::SpotLost => SPOT_LOST_SAMPLE,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct EscalationConfig {
+    pub start_volume: f32,
+    pub step: f32,
+    pub interval_seconds: u64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct SoundConfig {
+    pub spot_found_file: Option<PathBuf>,
+    pub spot_lost_file: Option<PathBuf>,
+    pub volume: f32,
+    pub repeat_count: Option<usize>,
+    pub max_duration_seconds: Option<u64>,
+    pub escalation: Option<EscalationConfig>,
+}
+
+impl Default for SoundConfig {
+    fn default() -> Self {
+        Self {
+            spot_found_file: None,
+            spot_lost_file: None,
+            volume: 1.0,
+            repeat_count: None,
+            max_duration_seconds: None,
+            escalation: None,
+        }
+    }
+}
+
+impl SoundConfig {
+    fn volume_at(&self, elapsed: Duration) -> f32 {
+        match self.escalation {
+            Some(escalation) if escalation.interval_seconds > 0 => {
+                let steps = elapsed.as_secs() / escalation.interval_seconds;
+                (escalation.start_volume + escalation.step * steps as f32).min(self.volume)
+            }
+            _ => self.volume,
+        }
+    }
+
+    fn is_expired(&self, elapsed: Duration) -> bool {
+        self.max_duration_seconds
+            .map(|max_duration_seconds| elapsed.as_secs() >= max_duration_seconds)
+            .unwrap_or(false)
+    }
+}
+
+fn read_sound(sound_file: &Option<PathBuf>, default_sample: DefaultSample) -> Result<Vec<u8>> {
+    if let Some(sound_file) = sound_file {
+        return Ok(fs::read(sound_file)?);
+    }
+    Ok(default_sample.bytes().to_vec())
+}
 
 pub async fn play_infinite_sound(receiver: Receiver<()>) -> Result<()> {
-    let sound_bytes = include_bytes!("../assets/sample.wav");
+    play_sound(
+        &SoundConfig::default(),
+        &None,
+        DefaultSample::SpotFound,
+        receiver,
+    )
+    .await
+}
 
-    let _wrapped_stream: SendWrapper<Rc<OutputStream>>;
-    let sink: Sink;
-    {
-        let (stream, stream_handle) = OutputStream::try_default()?;
-        sink = Sink::try_new(&stream_handle)?;
-        _wrapped_stream = SendWrapper::new(Rc::new(stream));
+fn create_sink() -> Result<(Sink, SendWrapper<Rc<OutputStream>>)> {
+    let (stream, stream_handle) = OutputStream::try_default()?;
+    let sink = Sink::try_new(&stream_handle)?;
+    Ok((sink, SendWrapper::new(Rc::new(stream))))
+}
+
+pub async fn play_sound(
+    config: &SoundConfig,
+    sound_file: &Option<PathBuf>,
+    default_sample: DefaultSample,
+    receiver: Receiver<()>,
+) -> Result<()> {
+    let (sink, _wrapped_stream) = match create_sink() {
+        std::result::Result::Ok(sink_and_stream) => sink_and_stream,
+        Err(err) => {
+            debug!("No audio device, falling back to terminal bell: {}", err);
+            return ring_bell_until(config, receiver).await;
+        }
+    };
+
+    let sound_bytes = read_sound(sound_file, default_sample)?;
+    match config.repeat_count {
+        Some(repeat_count) => {
+            for _ in 0..repeat_count {
+                sink.append(Decoder::new(Cursor::new(sound_bytes.clone()))?);
+            }
+        }
+        None => sink.append(Decoder::new(Cursor::new(sound_bytes))?.repeat_infinite()),
     }
 
-    let cursor = Cursor::new(sound_bytes.as_ref());
-    let decoder = Decoder::new(cursor)?;
-    sink.append(decoder.repeat_infinite());
-    receiver.await?;
+    let started = Instant::now();
+    sink.set_volume(config.volume_at(started.elapsed()));
+    let mut ticker = interval(SOUND_POLL_INTERVAL);
+    tokio::pin!(receiver);
+    loop {
+        tokio::select! {
+            _ = &mut receiver => break,
+            _ = ticker.tick() => {
+                if sink.empty() || config.is_expired(started.elapsed()) {
+                    break;
+                }
+                sink.set_volume(config.volume_at(started.elapsed()));
+            }
+        }
+    }
+    sink.stop();
 
     Ok(())
 }
+
+async fn ring_bell_until(config: &SoundConfig, receiver: Receiver<()>) -> Result<()> {
+    let started = Instant::now();
+    let mut rings = 0;
+    let mut ticker = interval(BELL_INTERVAL);
+    tokio::pin!(receiver);
+    loop {
+        tokio::select! {
+            _ = &mut receiver => break,
+            _ = ticker.tick() => {
+                let is_repeated = config
+                    .repeat_count
+                    .map(|repeat_count| rings >= repeat_count)
+                    .unwrap_or(false);
+                if is_repeated || config.is_expired(started.elapsed()) {
+                    break;
+                }
+                ring_bell()?;
+                rings += 1;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_samples_are_distinct_and_decodable() {
+        assert_ne!(
+            DefaultSample::SpotFound.bytes(),
+            DefaultSample::SpotLost.bytes()
+        );
+        for sample in [DefaultSample::SpotFound, DefaultSample::SpotLost] {
+            assert!(Decoder::new(Cursor::new(sample.bytes())).is_ok());
+        }
+    }
+}
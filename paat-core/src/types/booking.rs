@@ -0,0 +1,154 @@
+use super::{event::CodeWrapper, Direction};
+use crate::datetime::{get_service_datetime, service_datetime_to_local_time_string};
+use anyhow::{anyhow, Result};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+use strum::EnumProperty;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TicketEvent {
+    #[serde(rename(deserialize = "uid"), alias = "uuid")]
+    pub uuid: String,
+    #[serde(rename(deserialize = "dtstart"), alias = "start")]
+    pub start: String,
+    #[serde(rename(deserialize = "dtend"), alias = "end")]
+    pub end: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Vehicle {
+    #[serde(rename(deserialize = "regNumber"), alias = "plate_number")]
+    pub plate_number: String,
+    pub category: CodeWrapper,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PassengerCount {
+    pub category: CodeWrapper,
+    pub count: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Ticket {
+    #[serde(rename(deserialize = "uid"), alias = "uuid")]
+    pub uuid: String,
+    pub event: TicketEvent,
+    #[serde(default)]
+    pub direction: Option<CodeWrapper>,
+    #[serde(default)]
+    pub vehicle: Option<Vehicle>,
+    #[serde(default)]
+    pub passengers: Vec<PassengerCount>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Booking {
+    #[serde(rename(deserialize = "uid"), alias = "uuid")]
+    pub uuid: String,
+    #[serde(default)]
+    pub tickets: Vec<Ticket>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TicketResponse {
+    pub items: Vec<Ticket>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TicketChangeRequest<'a> {
+    pub event: TicketChangeEvent<'a>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TicketChangeEvent<'a> {
+    pub uid: &'a str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BookingDetails {
+    pub booking_id: String,
+    pub event_uuid: String,
+    pub direction: Direction,
+    pub date: NaiveDate,
+    pub departure: String,
+    pub vehicle: Option<Vehicle>,
+    pub passengers: Vec<PassengerCount>,
+}
+
+impl BookingDetails {
+    pub fn from_ticket(booking_id: &str, ticket: Ticket) -> Result<Self> {
+        let direction_code = ticket
+            .direction
+            .map(|direction| direction.code)
+            .ok_or_else(|| anyhow!("Booking {} does not show its line", booking_id))?;
+        let direction = Direction::from_abbreviation(&direction_code)
+            .ok_or_else(|| anyhow!("Unknown line {}", direction_code))?;
+        let start = get_service_datetime(&ticket.event.start)?;
+        Ok(Self {
+            booking_id: booking_id.to_string(),
+            departure: service_datetime_to_local_time_string(&ticket.event.start)?,
+            event_uuid: ticket.event.uuid,
+            direction,
+            date: start.naive_local().date(),
+            vehicle: ticket.vehicle,
+            passengers: ticket.passengers,
+        })
+    }
+
+    pub fn passenger_count(&self) -> u32 {
+        self.passengers
+            .iter()
+            .map(|passengers| passengers.count)
+            .sum()
+    }
+}
+
+impl Display for BookingDetails {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        write!(
+            fmt,
+            "{} {} on {}",
+            self.direction.get_str("Abbreviation").unwrap_or_default(),
+            self.departure,
+            self.date.format("%-d %B")
+        )?;
+        if let Some(vehicle) = &self.vehicle {
+            write!(fmt, " with {}", vehicle.plate_number)?;
+        }
+        match self.passenger_count() {
+            0 => Ok(()),
+            1 => fmt.write_str(", 1 passenger"),
+            count => write!(fmt, ", {} passengers", count),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn takes_booking_date_from_service_time() {
+        let ticket: Ticket = serde_json::from_str(
+            r#"{
+                "uid": "a1b2c3",
+                "event": {
+                    "uid": "e6b5e6d2",
+                    "dtstart": "2026-07-04T00:30:00.000+0300",
+                    "dtend": "2026-07-04T01:00:00.000+0300"
+                },
+                "direction": {"code": "VK"},
+                "passengers": [{"category": {"code": "ADULT"}, "count": 2}]
+            }"#,
+        )
+        .unwrap();
+        let booking_details = BookingDetails::from_ticket("ABC123", ticket).unwrap();
+        assert_eq!(booking_details.direction, Direction::VK);
+        assert_eq!(
+            booking_details.date,
+            NaiveDate::from_ymd_opt(2026, 7, 4).unwrap()
+        );
+        assert_eq!(booking_details.passenger_count(), 2);
+    }
+}
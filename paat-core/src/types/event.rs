@@ -5,7 +5,7 @@ use std::{
     fmt::{Display, Formatter, Result},
 };
 
-#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub struct Capacity {
     #[serde(rename(deserialize = "pcs"))]
     pub passengers: i32,
@@ -19,9 +19,21 @@ pub struct Capacity {
     pub dc: i32,
 }
 
+impl Capacity {
+    pub fn fields(&self) -> [(&'static str, i32); 5] {
+        [
+            ("passengers", self.passengers),
+            ("bc", self.bc),
+            ("small_vehicles", self.small_vehicles),
+            ("large_vehicles", self.large_vehicles),
+            ("dc", self.dc),
+        ]
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct CodeWrapper {
-    code: String,
+    pub code: String,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -48,6 +60,9 @@ pub type EventMap = BTreeMap<String, Event>;
 pub enum WaitForSpot {
     Done(Event),
     Waiting,
+    Expired,
+    Lost(Event),
+    Regained(Event),
 }
 
 impl Display for Event {
@@ -1,9 +1,24 @@
+pub mod booking;
 pub mod event;
 use crate::constants::LINES;
+use serde::{Deserialize, Serialize};
 use std::str::FromStr;
+use strum::EnumProperty;
 use strum_macros::{Display, EnumProperty, EnumString};
 
-#[derive(Display, Debug, Clone, Copy, EnumString, EnumProperty, Default)]
+#[derive(
+    Display,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    EnumString,
+    EnumProperty,
+    Default,
+    Serialize,
+    Deserialize,
+)]
 pub enum Direction {
     #[default]
     #[strum(props(Abbreviation = "HR"), to_string = "Heltermaa - Rohuküla")]
@@ -26,4 +41,16 @@ impl Direction {
         }
         None
     }
+
+    pub fn get_line_index(&self) -> Option<usize> {
+        let line = self.to_string();
+        LINES.iter().position(|known_line| *known_line == line)
+    }
+
+    pub fn from_abbreviation(abbreviation: &str) -> Option<Self> {
+        let abbreviation = abbreviation.to_uppercase();
+        (0..LINES.len())
+            .filter_map(Self::get_line_by_index)
+            .find(|direction| direction.get_str("Abbreviation") == Some(abbreviation.as_str()))
+    }
 }
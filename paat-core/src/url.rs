@@ -2,3 +2,34 @@ use const_format::formatcp;
 
 const BASE_URL: &str = "https://www.praamid.ee/online";
 pub const EVENTS_URL: &str = formatcp!("{}/events", BASE_URL);
+pub const BOOKINGS_URL: &str = formatcp!("{}/bookings", BASE_URL);
+
+pub const PORTAL_URL: &str = "https://www.praamid.ee/portal";
+pub const MAIN_BOOKING_URL: &str = formatcp!("{}/ticket/checkout/success;", PORTAL_URL);
+#[cfg(feature = "booking")]
+pub const PURCHASE_URL: &str = formatcp!("{}/ticket/purchase;", PORTAL_URL);
+pub const LANGUAGE_URL: &str = "lang=et";
+
+pub fn booking_url(booking_id: &str) -> String {
+    format!(
+        "{}bookingUid={};{}",
+        MAIN_BOOKING_URL, booking_id, LANGUAGE_URL
+    )
+}
+
+#[cfg(feature = "booking")]
+pub fn purchase_url() -> String {
+    format!("{}{}", PURCHASE_URL, LANGUAGE_URL)
+}
+
+pub fn booking_api_url(booking_id: &str) -> String {
+    format!("{}/{}", BOOKINGS_URL, booking_id)
+}
+
+pub fn tickets_api_url(booking_id: &str) -> String {
+    format!("{}/tickets", booking_api_url(booking_id))
+}
+
+pub fn ticket_api_url(booking_id: &str, ticket_id: &str) -> String {
+    format!("{}/{}", tickets_api_url(booking_id), ticket_id)
+}
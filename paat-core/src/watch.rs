@@ -0,0 +1,53 @@
+use crate::types::{event::Event, Direction};
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+pub const STALLED_AFTER_FAILURES: usize = 3;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct WatchStatus {
+    pub free_spots: Option<usize>,
+    pub last_success: Option<DateTime<Local>>,
+    pub consecutive_failures: usize,
+    pub last_error: Option<String>,
+    #[serde(default)]
+    pub expired: bool,
+}
+
+impl WatchStatus {
+    pub fn record_success(&mut self) {
+        self.last_success = Some(Local::now());
+        self.consecutive_failures = 0;
+    }
+
+    pub fn record_failure(&mut self, error: String) {
+        self.consecutive_failures += 1;
+        self.last_error = Some(error);
+    }
+
+    pub fn is_stalled(&self) -> bool {
+        self.consecutive_failures >= STALLED_AFTER_FAILURES
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Watch {
+    pub id: u64,
+    pub direction: Direction,
+    pub departure_date: NaiveDate,
+    pub event: Event,
+    #[serde(default)]
+    pub status: WatchStatus,
+}
+
+impl Watch {
+    pub fn new(id: u64, direction: Direction, departure_date: NaiveDate, event: Event) -> Self {
+        Self {
+            id,
+            direction,
+            departure_date,
+            event,
+            status: WatchStatus::default(),
+        }
+    }
+}
@@ -0,0 +1,173 @@
+const lines = new Map();
+const watches = new Map();
+
+const lineSelect = document.getElementById("line");
+const dateInput = document.getElementById("date");
+const message = document.getElementById("message");
+const eventsTable = document.getElementById("events");
+const watchesTable = document.getElementById("watches");
+
+const request = async (path, options) => {
+  const response = await fetch(path, options);
+  if (response.status === 204) {
+    return null;
+  }
+  const body = await response.json();
+  if (!response.ok) {
+    throw new Error(body.error || response.statusText);
+  }
+  return body;
+};
+
+const toTime = (datetime) => datetime.slice(11, 16);
+
+const cell = (row, content) => {
+  const td = row.insertCell();
+  if (content instanceof Node) {
+    td.appendChild(content);
+  } else {
+    td.textContent = content;
+  }
+  return td;
+};
+
+const button = (label, onClick) => {
+  const element = document.createElement("button");
+  element.textContent = label;
+  element.addEventListener("click", onClick);
+  return element;
+};
+
+const showError = (error) => {
+  message.textContent = error ? error.message : "";
+};
+
+const loadLines = async () => {
+  for (const line of await request("/lines")) {
+    lines.set(line.code, line.name);
+    lineSelect.add(new Option(line.name, line.code));
+  }
+  dateInput.value = new Date().toISOString().slice(0, 10);
+};
+
+const addWatch = async (event) => {
+  try {
+    const watch = await request("/watches", {
+      method: "POST",
+      headers: { "Content-Type": "application/json" },
+      body: JSON.stringify({
+        line: lineSelect.value,
+        date: dateInput.value,
+        event_uuid: event.uuid,
+      }),
+    });
+    updateWatch(watch);
+    showError(null);
+  } catch (error) {
+    showError(error);
+  }
+};
+
+const renderEvents = (events) => {
+  const body = eventsTable.tBodies[0];
+  body.replaceChildren();
+  for (const event of events) {
+    const row = body.insertRow();
+    cell(row, toTime(event.start));
+    cell(row, toTime(event.end));
+    cell(row, event.ship.code);
+    cell(row, event.capacities.small_vehicles);
+    cell(row, event.capacities.passengers);
+    cell(row, button("Track", () => addWatch(event)));
+  }
+  eventsTable.hidden = events.length === 0;
+  if (events.length === 0) {
+    showError(new Error("No sailings on this date"));
+  }
+};
+
+const describeStatus = (status) => {
+  if (status.expired) {
+    return ["Departed", "departed"];
+  }
+  if (status.free_spots !== null) {
+    return [`${status.free_spots} spot(s) free`, "found"];
+  }
+  if (status.consecutive_failures >= 3) {
+    return [`Stalled: ${status.last_error}`, "stalled"];
+  }
+  if (status.consecutive_failures > 0) {
+    return [`Waiting (${status.consecutive_failures} failed polls)`, "stalled"];
+  }
+  return ["Waiting", ""];
+};
+
+const removeWatch = async (id) => {
+  try {
+    await request(`/watches/${id}`, { method: "DELETE" });
+    watches.delete(id);
+    renderWatches();
+  } catch (error) {
+    showError(error);
+  }
+};
+
+const renderWatches = () => {
+  const body = watchesTable.tBodies[0];
+  body.replaceChildren();
+  const sorted = [...watches.values()].sort((left, right) => left.id - right.id);
+  for (const watch of sorted) {
+    const row = body.insertRow();
+    const [status, className] = describeStatus(watch.status);
+    cell(row, lines.get(watch.direction) || watch.direction);
+    cell(row, watch.departure_date);
+    cell(row, toTime(watch.event.start));
+    cell(row, status).className = className;
+    cell(
+      row,
+      watch.status.last_success
+        ? new Date(watch.status.last_success).toLocaleTimeString()
+        : "-"
+    );
+    cell(row, button("Stop", () => removeWatch(watch.id)));
+  }
+  watchesTable.hidden = sorted.length === 0;
+  document.getElementById("empty").hidden = sorted.length !== 0;
+};
+
+const updateWatch = (watch) => {
+  watches.set(watch.id, watch);
+  renderWatches();
+};
+
+const subscribe = () => {
+  const source = new EventSource("/watches/stream");
+  source.addEventListener("watch", (event) => updateWatch(JSON.parse(event.data)));
+};
+
+document.getElementById("search").addEventListener("submit", async (event) => {
+  event.preventDefault();
+  showError(null);
+  try {
+    const params = new URLSearchParams({ line: lineSelect.value, date: dateInput.value });
+    renderEvents(await request(`/events?${params}`));
+  } catch (error) {
+    eventsTable.hidden = true;
+    showError(error);
+  }
+});
+
+const start = async () => {
+  try {
+    await loadLines();
+    for (const watch of await request("/watches")) {
+      watches.set(watch.id, watch);
+    }
+    renderWatches();
+    subscribe();
+  } catch (error) {
+    showError(error);
+  }
+};
+
+start();
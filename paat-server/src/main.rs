@@ -0,0 +1,74 @@
+mod error;
+mod routes;
+mod store;
+mod watches;
+mod web;
+
+use anyhow::Result;
+use env_logger::init;
+use log::info;
+use paat_core::{
+    analytics::{AdaptiveSchedule, SpotStatistics},
+    client::Client,
+    config::Config,
+    constants::TIMEOUT_BETWEEN_REQUESTS,
+    metrics::Metrics,
+    mqtt::MqttPublisher,
+    notification::Dispatcher,
+    recorder::Recorder,
+};
+use routes::create_router;
+use std::{net::SocketAddr, path::PathBuf, sync::Arc, time::Duration};
+use tokio::signal::ctrl_c;
+use watches::Watches;
+
+const DEFAULT_ADDRESS: &str = "127.0.0.1:8080";
+const DEFAULT_WATCHES_FILE: &str = "paat-watches.json";
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let timeout_between_requests = std::env::var("TIMEOUT_BETWEEN_REQUESTS")
+        .map(|timeout| timeout.parse::<u64>().unwrap_or(TIMEOUT_BETWEEN_REQUESTS))
+        .unwrap_or(TIMEOUT_BETWEEN_REQUESTS);
+    init();
+    let address = std::env::var("PAAT_SERVER_ADDRESS")
+        .unwrap_or_else(|_| DEFAULT_ADDRESS.to_string())
+        .parse::<SocketAddr>()?;
+    let watches_file = std::env::var("PAAT_WATCHES_FILE")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(DEFAULT_WATCHES_FILE));
+
+    let config = Config::load()?;
+    let metrics = Arc::new(Metrics::new()?);
+    let mut client = Client::new(Duration::from_secs(timeout_between_requests))
+        .with_stop_before_departure(config.stop_before_departure())
+        .with_observer(metrics.clone());
+    if let Some(mqtt_config) = config.mqtt {
+        client = client.with_observer(Arc::new(MqttPublisher::new(mqtt_config)?));
+    }
+    if let Some(recorder_config) = config.recorder {
+        let recorder = Recorder::new(recorder_config.clone())?;
+        if let Some(schedule_config) = config.schedule {
+            let statistics = SpotStatistics::load(&recorder_config.path)?;
+            client =
+                client.with_schedule(Arc::new(AdaptiveSchedule::new(statistics, schedule_config)));
+        }
+        client = client.with_observer(Arc::new(recorder));
+    }
+    let watches = Watches::new(
+        client,
+        Dispatcher::from_config(&config.notifiers),
+        watches_file,
+    )
+    .with_continuous(config.continuous);
+    watches.restore().await?;
+
+    info!("Listening on {}", address);
+    axum::Server::bind(&address)
+        .serve(create_router(watches, metrics).into_make_service())
+        .with_graceful_shutdown(async {
+            let _ = ctrl_c().await;
+        })
+        .await?;
+    Ok(())
+}
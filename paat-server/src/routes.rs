@@ -0,0 +1,159 @@
+use crate::{
+    error::{ApiError, ApiResult},
+    watches::Watches,
+    web::create_web_router,
+};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::sse::{Event as SseEvent, KeepAlive, Sse},
+    routing::get,
+    Json, Router,
+};
+use chrono::NaiveDate;
+use futures::{Stream, StreamExt};
+use paat_core::{
+    constants::LINES,
+    datetime::service_datetime_to_local_time_string,
+    metrics::Metrics,
+    types::{event::Event, Direction},
+    watch::Watch,
+};
+use serde::{Deserialize, Serialize};
+use std::{convert::Infallible, sync::Arc};
+use strum::EnumProperty;
+use tokio_stream::wrappers::BroadcastStream;
+
+#[derive(Deserialize)]
+pub struct EventsQuery {
+    line: Direction,
+    date: NaiveDate,
+}
+
+#[derive(Deserialize)]
+pub struct CreateWatch {
+    line: Direction,
+    date: NaiveDate,
+    time: Option<String>,
+    event_uuid: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct Line {
+    code: String,
+    name: String,
+}
+
+pub fn create_router(watches: Watches, metrics: Arc<Metrics>) -> Router {
+    create_web_router()
+        .route("/lines", get(list_lines))
+        .route("/events", get(list_events))
+        .route("/watches", get(list_watches).post(create_watch))
+        .route("/watches/stream", get(stream_watches))
+        .route("/watches/:id", get(get_watch).delete(delete_watch))
+        .with_state(watches)
+        .merge(
+            Router::new()
+                .route("/metrics", get(render_metrics))
+                .with_state(metrics),
+        )
+}
+
+async fn fetch_sorted_events(
+    watches: &Watches,
+    date: &NaiveDate,
+    line: &Direction,
+) -> ApiResult<Vec<Event>> {
+    let event_map = watches.client().fetch_events(date, line).await?;
+    let mut events = event_map.into_values().collect::<Vec<Event>>();
+    events.sort_by_key(|event| event.start.clone());
+    Ok(events)
+}
+
+async fn list_lines() -> Json<Vec<Line>> {
+    Json(
+        (0..LINES.len())
+            .filter_map(Direction::get_line_by_index)
+            .map(|direction| Line {
+                code: direction
+                    .get_str("Abbreviation")
+                    .unwrap_or_default()
+                    .to_string(),
+                name: direction.to_string(),
+            })
+            .collect(),
+    )
+}
+
+async fn list_events(
+    State(watches): State<Watches>,
+    Query(query): Query<EventsQuery>,
+) -> ApiResult<Json<Vec<Event>>> {
+    Ok(Json(
+        fetch_sorted_events(&watches, &query.date, &query.line).await?,
+    ))
+}
+
+async fn list_watches(State(watches): State<Watches>) -> Json<Vec<Watch>> {
+    Json(watches.list().await)
+}
+
+async fn get_watch(State(watches): State<Watches>, Path(id): Path<u64>) -> ApiResult<Json<Watch>> {
+    watches
+        .get(id)
+        .await
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found(format!("No watch with id {}", id)))
+}
+
+async fn create_watch(
+    State(watches): State<Watches>,
+    Json(request): Json<CreateWatch>,
+) -> ApiResult<(StatusCode, Json<Watch>)> {
+    let events = fetch_sorted_events(&watches, &request.date, &request.line).await?;
+    let event = events
+        .into_iter()
+        .find(|event| match (&request.event_uuid, &request.time) {
+            (Some(event_uuid), _) => &event.uuid == event_uuid,
+            (None, Some(time)) => {
+                service_datetime_to_local_time_string(&event.start)
+                    .ok()
+                    .as_ref()
+                    == Some(time)
+            }
+            (None, None) => false,
+        })
+        .ok_or_else(|| ApiError::bad_request("No matching sailing, provide time or event_uuid"))?;
+    let watch = watches
+        .add(Watch::new(0, request.line, request.date, event))
+        .await?;
+    Ok((StatusCode::CREATED, Json(watch)))
+}
+
+async fn delete_watch(
+    State(watches): State<Watches>,
+    Path(id): Path<u64>,
+) -> ApiResult<StatusCode> {
+    match watches.remove(id).await? {
+        Some(_) => Ok(StatusCode::NO_CONTENT),
+        None => Err(ApiError::not_found(format!("No watch with id {}", id))),
+    }
+}
+
+async fn stream_watches(
+    State(watches): State<Watches>,
+) -> Sse<impl Stream<Item = Result<SseEvent, Infallible>>> {
+    let stream = BroadcastStream::new(watches.subscribe()).filter_map(|update| async move {
+        let watch = update.ok()?;
+        SseEvent::default()
+            .event("watch")
+            .json_data(watch)
+            .ok()
+            .map(Ok)
+    });
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+async fn render_metrics(State(metrics): State<Arc<Metrics>>) -> ApiResult<String> {
+    Ok(metrics.render()?)
+}
@@ -0,0 +1,141 @@
+use chrono::NaiveDate;
+use paat_core::{
+    types::{
+        event::{Capacity, CodeWrapper, Event},
+        Direction,
+    },
+    watch::{Watch, WatchStatus},
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+struct StoredCapacity {
+    passengers: i32,
+    bc: i32,
+    small_vehicles: i32,
+    large_vehicles: i32,
+    dc: i32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredEvent {
+    uuid: String,
+    capacities: StoredCapacity,
+    price_list: CodeWrapper,
+    transportation_type: CodeWrapper,
+    ship: CodeWrapper,
+    status: String,
+    start: String,
+    end: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct StoredWatch {
+    id: u64,
+    direction: Direction,
+    departure_date: NaiveDate,
+    event: StoredEvent,
+    #[serde(default)]
+    status: WatchStatus,
+}
+
+impl From<&Watch> for StoredWatch {
+    fn from(watch: &Watch) -> Self {
+        let event = &watch.event;
+        let capacities = &event.capacities;
+        Self {
+            id: watch.id,
+            direction: watch.direction,
+            departure_date: watch.departure_date,
+            event: StoredEvent {
+                uuid: event.uuid.clone(),
+                capacities: StoredCapacity {
+                    passengers: capacities.passengers,
+                    bc: capacities.bc,
+                    small_vehicles: capacities.small_vehicles,
+                    large_vehicles: capacities.large_vehicles,
+                    dc: capacities.dc,
+                },
+                price_list: event.price_list.clone(),
+                transportation_type: event.transportation_type.clone(),
+                ship: event.ship.clone(),
+                status: event.status.clone(),
+                start: event.start.clone(),
+                end: event.end.clone(),
+            },
+            status: watch.status.clone(),
+        }
+    }
+}
+
+impl From<StoredWatch> for Watch {
+    fn from(stored: StoredWatch) -> Self {
+        let event = stored.event;
+        let capacities = event.capacities;
+        Self {
+            id: stored.id,
+            direction: stored.direction,
+            departure_date: stored.departure_date,
+            event: Event {
+                uuid: event.uuid,
+                capacities: Capacity {
+                    passengers: capacities.passengers,
+                    bc: capacities.bc,
+                    small_vehicles: capacities.small_vehicles,
+                    large_vehicles: capacities.large_vehicles,
+                    dc: capacities.dc,
+                },
+                price_list: event.price_list,
+                transportation_type: event.transportation_type,
+                ship: event.ship,
+                status: event.status,
+                start: event.start,
+                end: event.end,
+            },
+            status: stored.status,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_watch_through_json() {
+        let code = |code: &str| CodeWrapper {
+            code: code.to_string(),
+        };
+        let event = Event {
+            uuid: String::from("e6b5e6d2"),
+            capacities: Capacity {
+                passengers: 120,
+                bc: 4,
+                small_vehicles: 2,
+                large_vehicles: 0,
+                dc: 1,
+            },
+            price_list: code("VK"),
+            transportation_type: code("REGULAR"),
+            ship: code("TIIU"),
+            status: String::from("IN_SALE"),
+            start: String::from("2026-07-03T17:30:00.000+0300"),
+            end: String::from("2026-07-03T18:00:00.000+0300"),
+        };
+        let mut watch = Watch::new(
+            7,
+            Direction::VK,
+            NaiveDate::from_ymd_opt(2026, 7, 3).unwrap(),
+            event,
+        );
+        watch.status.free_spots = Some(2);
+
+        let json = serde_json::to_string(&StoredWatch::from(&watch)).unwrap();
+        let restored = Watch::from(serde_json::from_str::<StoredWatch>(&json).unwrap());
+        assert_eq!(restored.id, watch.id);
+        assert_eq!(restored.direction, watch.direction);
+        assert_eq!(restored.departure_date, watch.departure_date);
+        assert_eq!(restored.event, watch.event);
+        assert_eq!(restored.status, watch.status);
+    }
+}
@@ -0,0 +1,261 @@
+use crate::store::StoredWatch;
+use anyhow::{Context, Result};
+use futures::StreamExt;
+use log::{debug, warn};
+use paat_core::{
+    client::Client,
+    notification::{Dispatcher, Notification, NotificationKind},
+    types::event::WaitForSpot,
+    watch::Watch,
+};
+use std::{collections::BTreeMap, path::PathBuf, sync::Arc};
+use tokio::{
+    fs,
+    sync::{broadcast, Mutex},
+    task::JoinHandle,
+};
+
+const UPDATE_CHANNEL_CAPACITY: usize = 64;
+
+// Polls only touch the last poll time, so the store is written when one of these changes
+fn persisted_state(watch: &Watch) -> (bool, bool, bool) {
+    (
+        watch.status.free_spots.is_some(),
+        watch.status.expired,
+        watch.status.is_stalled(),
+    )
+}
+
+struct WatchEntry {
+    watch: Watch,
+    handle: Option<JoinHandle<()>>,
+}
+
+#[derive(Default)]
+struct WatchList {
+    next_id: u64,
+    entries: BTreeMap<u64, WatchEntry>,
+}
+
+#[derive(Clone)]
+pub struct Watches {
+    client: Client,
+    dispatcher: Arc<Dispatcher>,
+    store_path: PathBuf,
+    watch_list: Arc<Mutex<WatchList>>,
+    store_lock: Arc<Mutex<()>>,
+    updates: broadcast::Sender<Watch>,
+    continuous: bool,
+}
+
+impl Watches {
+    pub fn new(client: Client, dispatcher: Dispatcher, store_path: PathBuf) -> Self {
+        let (updates, _) = broadcast::channel(UPDATE_CHANNEL_CAPACITY);
+        Self {
+            client,
+            dispatcher: Arc::new(dispatcher),
+            store_path,
+            watch_list: Arc::new(Mutex::new(WatchList::default())),
+            store_lock: Arc::new(Mutex::new(())),
+            updates,
+            continuous: false,
+        }
+    }
+
+    pub fn with_continuous(mut self, continuous: bool) -> Self {
+        self.continuous = continuous;
+        self
+    }
+
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Watch> {
+        self.updates.subscribe()
+    }
+
+    pub async fn restore(&self) -> Result<()> {
+        if !self.store_path.exists() {
+            return Ok(());
+        }
+        let content = fs::read_to_string(&self.store_path)
+            .await
+            .with_context(|| format!("Failed to read {}", self.store_path.display()))?;
+        let watches = serde_json::from_str::<Vec<StoredWatch>>(&content)
+            .with_context(|| format!("Failed to parse {}", self.store_path.display()))?;
+        let mut watch_list = self.watch_list.lock().await;
+        for watch in watches.into_iter().map(Watch::from) {
+            watch_list.next_id = watch_list.next_id.max(watch.id);
+            let handle = (!watch.status.expired).then(|| self.spawn_monitor(watch.clone()));
+            watch_list
+                .entries
+                .insert(watch.id, WatchEntry { watch, handle });
+        }
+        Ok(())
+    }
+
+    // The store lock keeps writes in order without holding the watch list during the write
+    async fn persist(&self) -> Result<()> {
+        let _store_guard = self.store_lock.lock().await;
+        let watches = self
+            .watch_list
+            .lock()
+            .await
+            .entries
+            .values()
+            .map(|entry| StoredWatch::from(&entry.watch))
+            .collect::<Vec<StoredWatch>>();
+        fs::write(&self.store_path, serde_json::to_string_pretty(&watches)?)
+            .await
+            .with_context(|| format!("Failed to write {}", self.store_path.display()))
+    }
+
+    pub async fn list(&self) -> Vec<Watch> {
+        self.watch_list
+            .lock()
+            .await
+            .entries
+            .values()
+            .map(|entry| entry.watch.clone())
+            .collect()
+    }
+
+    pub async fn get(&self, id: u64) -> Option<Watch> {
+        self.watch_list
+            .lock()
+            .await
+            .entries
+            .get(&id)
+            .map(|entry| entry.watch.clone())
+    }
+
+    pub async fn add(&self, watch: Watch) -> Result<Watch> {
+        let mut watch_list = self.watch_list.lock().await;
+        watch_list.next_id += 1;
+        let watch = Watch {
+            id: watch_list.next_id,
+            ..watch
+        };
+        let handle = Some(self.spawn_monitor(watch.clone()));
+        watch_list.entries.insert(
+            watch.id,
+            WatchEntry {
+                watch: watch.clone(),
+                handle,
+            },
+        );
+        drop(watch_list);
+        self.persist().await?;
+        let _ = self.updates.send(watch.clone());
+        Ok(watch)
+    }
+
+    pub async fn remove(&self, id: u64) -> Result<Option<Watch>> {
+        let mut watch_list = self.watch_list.lock().await;
+        let entry = watch_list.entries.remove(&id);
+        if let Some(handle) = entry.as_ref().and_then(|entry| entry.handle.as_ref()) {
+            handle.abort();
+        }
+        drop(watch_list);
+        self.persist().await?;
+        Ok(entry.map(|entry| entry.watch))
+    }
+
+    async fn update<F>(&self, id: u64, update: F) -> Option<Watch>
+    where
+        F: FnOnce(&mut Watch),
+    {
+        let (watch, is_transition) = {
+            let mut watch_list = self.watch_list.lock().await;
+            let entry = watch_list.entries.get_mut(&id)?;
+            let previous_state = persisted_state(&entry.watch);
+            update(&mut entry.watch);
+            (
+                entry.watch.clone(),
+                persisted_state(&entry.watch) != previous_state,
+            )
+        };
+        if is_transition {
+            if let Err(err) = self.persist().await {
+                warn!("{}", err);
+            }
+        }
+        let _ = self.updates.send(watch.clone());
+        Some(watch)
+    }
+
+    fn spawn_monitor(&self, watch: Watch) -> JoinHandle<()> {
+        let watches = self.clone();
+        tokio::spawn(async move {
+            let mut wait_stream = Box::pin(watches.client.create_spot_stream(
+                &watch.departure_date,
+                &watch.direction,
+                &watch.event.uuid,
+                watches.continuous,
+            ));
+            while let Some(wait_result) = wait_stream.next().await {
+                let found = matches!(wait_result, Ok(WaitForSpot::Done(_)));
+                let mut notification = None;
+                let updated = watches
+                    .update(watch.id, |watch| match wait_result {
+                        Ok(WaitForSpot::Expired) => watch.status.expired = true,
+                        Ok(wait_response) => {
+                            watch.status.record_success();
+                            match wait_response {
+                                WaitForSpot::Done(event) => {
+                                    if watch.status.free_spots.is_none() {
+                                        notification = Some(Notification::new(
+                                            NotificationKind::SpotFound,
+                                            event.clone(),
+                                            watch.direction,
+                                            watch.departure_date,
+                                        ));
+                                    }
+                                    watch.status.free_spots =
+                                        Some(event.capacities.small_vehicles as usize);
+                                    watch.event = event;
+                                }
+                                WaitForSpot::Lost(event) => {
+                                    notification = Some(Notification::new(
+                                        NotificationKind::SpotLost,
+                                        event.clone(),
+                                        watch.direction,
+                                        watch.departure_date,
+                                    ));
+                                    watch.status.free_spots = None;
+                                    watch.event = event;
+                                }
+                                WaitForSpot::Regained(event) => {
+                                    notification = Some(Notification::new(
+                                        NotificationKind::SpotRegained,
+                                        event.clone(),
+                                        watch.direction,
+                                        watch.departure_date,
+                                    ));
+                                    watch.status.free_spots =
+                                        Some(event.capacities.small_vehicles as usize);
+                                    watch.event = event;
+                                }
+                                WaitForSpot::Waiting | WaitForSpot::Expired => {}
+                            }
+                        }
+                        Err(err) => watch.status.record_failure(err.to_string()),
+                    })
+                    .await;
+                let expired = match updated {
+                    Some(watch) => watch.status.expired,
+                    None => break,
+                };
+                if let Some(notification) = notification {
+                    if let Err(err) = watches.dispatcher.dispatch(&notification).await {
+                        debug!("{}", err);
+                    }
+                }
+                if expired || (found && !watches.continuous) {
+                    break;
+                }
+            }
+        })
+    }
+}
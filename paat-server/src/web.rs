@@ -0,0 +1,32 @@
+use axum::{
+    http::header::CONTENT_TYPE,
+    response::{Html, IntoResponse},
+    routing::get,
+    Router,
+};
+
+const INDEX_HTML: &str = include_str!("../assets/index.html");
+const APP_JS: &str = include_str!("../assets/app.js");
+const STYLE_CSS: &str = include_str!("../assets/style.css");
+
+pub fn create_web_router<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    Router::new()
+        .route("/", get(index))
+        .route("/app.js", get(app_js))
+        .route("/style.css", get(style_css))
+}
+
+async fn index() -> Html<&'static str> {
+    Html(INDEX_HTML)
+}
+
+async fn app_js() -> impl IntoResponse {
+    ([(CONTENT_TYPE, "application/javascript")], APP_JS)
+}
+
+async fn style_css() -> impl IntoResponse {
+    ([(CONTENT_TYPE, "text/css")], STYLE_CSS)
+}
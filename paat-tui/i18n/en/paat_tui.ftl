@@ -9,6 +9,8 @@ usage-guide-text =
     s-      to clear unfinished events
     l-      to clear all events
     f-      to kill the alarm
+    b-      to attach a booking to a tracked ferry
+    n-      to look up a booking and track a new ferry for it
 departure-date = Departure date
 monday-character = M
 tuesday-character = T
@@ -37,3 +39,11 @@ direction = Direction
 date = Date
 time = Time
 spots = Spot(s)
+health = Health
+stalled = Stalled
+departed = Departed
+last-poll = Last poll
+failures = Failures
+booking = Booking
+booking-id = Booking ID
+booking-starting = Starting
@@ -30,3 +30,11 @@ direction = Suund
 date = Kuupäev
 time = Aeg
 spots = koht(a)
+health = Seisund
+stalled = Peatunud
+departed = Väljus
+last-poll = Viimane päring
+failures = Vead
+booking = Broneering
+booking-id = Broneeringu number
+booking-starting = Alustan
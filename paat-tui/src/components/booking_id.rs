@@ -0,0 +1,72 @@
+use super::hidden_handler::api_event_message;
+use crate::{localization::fl, messages::Message, ports::ApiEvent};
+use tui_realm_stdlib::Input;
+use tuirealm::{
+    command::{Cmd, CmdResult, Direction},
+    event::{Key, KeyEvent, KeyModifiers},
+    props::{Alignment, BorderType, Borders, Color, InputType},
+    Component, Event, MockComponent, State, StateValue,
+};
+
+#[derive(MockComponent)]
+pub struct BookingId {
+    component: Input,
+}
+
+impl Default for BookingId {
+    fn default() -> Self {
+        Self {
+            component: Input::default()
+                .borders(
+                    Borders::default()
+                        .modifiers(BorderType::Rounded)
+                        .color(Color::Yellow),
+                )
+                .input_type(InputType::Text)
+                .title(fl!("booking-id"), Alignment::Left),
+        }
+    }
+}
+
+impl Component<Message, ApiEvent> for BookingId {
+    fn on(&mut self, event: Event<ApiEvent>) -> Option<Message> {
+        let command = match event {
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => Cmd::Move(Direction::Left),
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => Cmd::Move(Direction::Right),
+            Event::Keyboard(KeyEvent {
+                code: Key::Backspace,
+                ..
+            }) => Cmd::Delete,
+            Event::Keyboard(KeyEvent {
+                code: Key::Delete, ..
+            }) => Cmd::Cancel,
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(character),
+                modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+            }) => Cmd::Type(character),
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter,
+                modifiers: KeyModifiers::NONE,
+            }) => Cmd::Submit,
+            Event::Keyboard(KeyEvent {
+                code: Key::Esc,
+                modifiers: KeyModifiers::NONE,
+            }) => return Some(Message::BookingIdSubmitted(String::new())),
+            // Shortcuts are paused while typing, so API events are forwarded from here
+            Event::User(api_event) => return api_event_message(api_event),
+            _ => Cmd::None,
+        };
+
+        match self.perform(command) {
+            CmdResult::Changed(_) => Some(Message::BookingIdChanged),
+            CmdResult::Submit(State::One(StateValue::String(booking_id))) => {
+                Some(Message::BookingIdSubmitted(booking_id))
+            }
+            _ => None,
+        }
+    }
+}
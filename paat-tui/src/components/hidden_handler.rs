@@ -5,6 +5,23 @@ use tuirealm::{
     Component, Event, MockComponent,
 };
 
+pub fn api_event_message(api_event: ApiEvent) -> Option<Message> {
+    match api_event {
+        ApiEvent::FetchedEvents(events) => Some(Message::EventsReceived(events)),
+        ApiEvent::WaitResult(wait_result) => Some(Message::WaitResultReceived(wait_result)),
+        ApiEvent::WaitError(wait_error) => Some(Message::WaitErrorReceived(wait_error)),
+        ApiEvent::BookingUpdate(booking_update) => {
+            Some(Message::BookingUpdateReceived(booking_update))
+        }
+        ApiEvent::BookingError(booking_error) => Some(Message::BookingErrorReceived(booking_error)),
+        ApiEvent::BookingFound(preselection) => Some(Message::BookingFoundReceived(preselection)),
+        ApiEvent::BookingLookupError(lookup_error) => {
+            Some(Message::BookingLookupErrorReceived(lookup_error))
+        }
+        ApiEvent::NoOperation => Some(Message::TickFromListener),
+    }
+}
+
 #[derive(MockComponent, Default)]
 pub struct HiddenHandler {
     component: Phantom,
@@ -37,15 +54,15 @@ impl Component<Message, ApiEvent> for HiddenHandler {
                 code: Key::Char('f'),
                 modifiers: KeyModifiers::NONE,
             }) => Some(Message::KillTheAlarm),
-            Event::User(ApiEvent::FetchedEvents(events)) => {
-                return Some(Message::EventsReceived(events));
-            }
-            Event::User(ApiEvent::WaitResult(wait_result)) => {
-                return Some(Message::WaitResultReceived(wait_result));
-            }
-            Event::User(ApiEvent::NoOperation) => {
-                return Some(Message::TickFromListener);
-            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('b'),
+                modifiers: KeyModifiers::NONE,
+            }) => Some(Message::AttachBooking),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('n'),
+                modifiers: KeyModifiers::NONE,
+            }) => Some(Message::PreselectBooking),
+            Event::User(api_event) => api_event_message(api_event),
             _ => None,
         }
     }
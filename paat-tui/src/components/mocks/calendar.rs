@@ -240,6 +240,9 @@ impl MockComponent for Calendar {
     }
 
     fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        if let (Attribute::Value, AttrValue::String(calendar_date)) = (attr, &value) {
+            self.states.selected_date = calendar_date.clone();
+        }
         self.props.set(attr, value);
     }
 
@@ -1,3 +1,4 @@
+mod booking_id;
 mod departure_date;
 mod header;
 mod hidden_handler;
@@ -6,13 +7,14 @@ mod select_ferry;
 mod select_line;
 mod tracking_list;
 
+pub use booking_id::BookingId;
 pub use departure_date::DepartureDate;
 pub use header::AppHeader;
 pub use hidden_handler::HiddenHandler;
 pub use mocks::HeaderAttributes;
 pub use select_ferry::SelectFerry;
 pub use select_line::SelectLine;
-pub use tracking_list::{TrackingList, TrackingListElement};
+pub use tracking_list::{BookingState, TrackingList, TrackingListElement};
 
 #[derive(Debug, Eq, PartialEq, Clone, Hash)]
 pub enum ComponentId {
@@ -22,4 +24,5 @@ pub enum ComponentId {
     SelectLine,
     HiddenHandler,
     TrackingList,
+    BookingId,
 }
@@ -2,46 +2,107 @@ use crate::localization::fl;
 use crate::messages::Message;
 use crate::ports::ApiEvent;
 use chrono::NaiveDate;
+use paat_core::notification::{Notification, NotificationKind};
 use paat_core::types::event::Event as PaatEvent;
 use paat_core::types::Direction;
+use paat_core::watch::WatchStatus;
 use tui_realm_stdlib::Table;
+use tuirealm::command::{Cmd, Direction as CmdDirection};
+use tuirealm::event::{Key, KeyEvent, KeyModifiers};
 use tuirealm::props::{Alignment, BorderType, Borders, Color, TableBuilder, TextSpan};
-use tuirealm::{AttrValue, Attribute};
+use tuirealm::{AttrValue, Attribute, State, StateValue};
 use tuirealm::{Component, Event, MockComponent};
 
+const LAST_POLL_FORMAT: &str = "%H:%M:%S";
+const HEADER_ROWS: usize = 3;
+const ROWS_PER_TRACK: usize = 2;
+
 #[derive(MockComponent)]
 pub struct TrackingList {
     component: Table,
 }
 
+#[derive(PartialEq, Clone, PartialOrd, Eq, Debug)]
+pub enum BookingState {
+    Attached(String),
+    Running(String),
+    Changed(String),
+    Failed(String),
+}
+
 #[derive(Clone, Default)]
 pub struct TrackingListElement {
     direction: Direction,
-    date: String,
+    date: NaiveDate,
     time: String,
     pub counter: usize,
     pub event_uuid: String,
-    pub free_spots: Option<usize>,
+    pub status: WatchStatus,
+    pub booking_id: Option<String>,
+    pub booking: Option<BookingState>,
 }
 
 impl TrackingListElement {
     pub fn new(direction: Option<Direction>, date: Option<NaiveDate>, event: &PaatEvent) -> Self {
         Self {
             direction: direction.unwrap(),
-            date: date.unwrap().to_string(),
+            date: date.unwrap(),
             time: event.to_string(),
             counter: 0,
             event_uuid: event.uuid.clone(),
-            free_spots: None,
+            status: WatchStatus::default(),
+            booking_id: None,
+            booking: None,
         }
     }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    pub fn can_change_booking(&self) -> bool {
+        self.booking_id.is_some()
+            && !matches!(
+                self.booking,
+                Some(BookingState::Running(_))
+                    | Some(BookingState::Changed(_))
+                    | Some(BookingState::Failed(_))
+            )
+    }
+
+    pub fn notification(&self, kind: NotificationKind, event: PaatEvent) -> Notification {
+        Notification::new(kind, event, self.direction, self.date)
+    }
+
+    pub fn description(&self) -> String {
+        format!("{} {} {}", self.direction, self.date, self.time)
+    }
 }
 
 impl TrackingList {
-    fn create_loader(count: usize, spots: Option<usize>) -> TextSpan {
-        if let Some(spots) = spots {
+    pub fn track_row(track_index: usize) -> usize {
+        HEADER_ROWS + track_index * ROWS_PER_TRACK
+    }
+
+    pub fn hidden_row() -> usize {
+        HEADER_ROWS - 1
+    }
+
+    fn create_loader(track: &TrackingListElement) -> TextSpan {
+        if track.status.expired {
+            return TextSpan::from(fl!("departed")).fg(Color::DarkGray);
+        }
+        if let Some(spots) = track.status.free_spots {
             return TextSpan::from(format!("{} {} 🥳", spots, fl!("spots")));
         }
+        if track.status.is_stalled() {
+            return TextSpan::from(format!("⚠️ {}", fl!("stalled"))).fg(Color::Red);
+        }
+        let count = track.counter;
         let mut loader = String::new();
         if (count % 10) > 5 {
             loader.push_str("🙉");
@@ -51,11 +112,52 @@ impl TrackingList {
         TextSpan::from(loader)
     }
 
+    fn create_health(track: &TrackingListElement) -> TextSpan {
+        let status = &track.status;
+        let last_poll = status
+            .last_success
+            .map(|last_success| last_success.format(LAST_POLL_FORMAT).to_string())
+            .unwrap_or_else(|| String::from("-"));
+        let health = format!("{} {}", fl!("last-poll"), last_poll);
+        if status.consecutive_failures == 0 {
+            return TextSpan::from(health);
+        }
+        let error = status.last_error.clone().unwrap_or_default();
+        TextSpan::from(format!(
+            "{} | {}: {} | {}",
+            health,
+            fl!("failures"),
+            status.consecutive_failures,
+            error
+        ))
+        .fg(Color::Red)
+    }
+
+    fn create_booking(track: &TrackingListElement) -> TextSpan {
+        match (&track.booking, &track.booking_id) {
+            (Some(BookingState::Attached(details)), _) => TextSpan::from(details),
+            (Some(BookingState::Running(progress)), _) => {
+                TextSpan::from(format!("⏳ {}", progress)).fg(Color::Yellow)
+            }
+            (Some(BookingState::Changed(outcome)), _) => {
+                TextSpan::from(format!("✅ {}", outcome)).fg(Color::Green)
+            }
+            (Some(BookingState::Failed(outcome)), _) => {
+                TextSpan::from(format!("❌ {}", outcome)).fg(Color::Red)
+            }
+            (None, Some(booking_id)) => TextSpan::from(booking_id),
+            (None, None) => TextSpan::from("-"),
+        }
+    }
+
     fn render_table_header(builder: &mut TableBuilder) {
         builder
             .add_col(TextSpan::from(format!("{}", fl!("direction"))).bold())
             .add_col(TextSpan::from(format!("{}", fl!("date"),)).bold())
             .add_col(TextSpan::from(format!("{}", fl!("time"))).bold())
+            .add_col(TextSpan::from("  "))
+            .add_col(TextSpan::from(fl!("health")).bold())
+            .add_col(TextSpan::from(fl!("booking")).bold())
             .add_row()
             .add_col(TextSpan::from("  "))
             .add_row()
@@ -67,11 +169,18 @@ impl TrackingList {
         let mut builder = TableBuilder::default();
         Self::render_table_header(&mut builder);
         for track in tracks {
+            let text_color = if track.status.expired {
+                Color::DarkGray
+            } else {
+                Color::Reset
+            };
             builder
-                .add_col(TextSpan::from(format!("{}", track.direction)))
-                .add_col(TextSpan::from(format!("{}", track.date)))
-                .add_col(TextSpan::from(format!("{}", track.time)))
-                .add_col(Self::create_loader(track.counter, track.free_spots))
+                .add_col(TextSpan::from(format!("{}", track.direction)).fg(text_color))
+                .add_col(TextSpan::from(format!("{}", track.date)).fg(text_color))
+                .add_col(TextSpan::from(format!("{}", track.time)).fg(text_color))
+                .add_col(Self::create_loader(&track))
+                .add_col(Self::create_health(&track).fg(text_color))
+                .add_col(Self::create_booking(&track))
                 .add_row()
                 .add_col(TextSpan::from("  "))
                 .add_row();
@@ -92,15 +201,50 @@ impl Default for TrackingList {
                         .modifiers(BorderType::Rounded)
                         .color(Color::Yellow),
                 )
-                .widths(&[40, 20, 20, 20])
+                .widths(&[20, 12, 12, 12, 22, 22])
                 .title(fl!("track-list"), Alignment::Center)
-                .table(builder.build()),
+                .scroll(true)
+                .highlighted_color(Color::LightYellow)
+                .table(builder.build())
+                .selected_line(Self::hidden_row()),
         }
     }
 }
 
 impl Component<Message, ApiEvent> for TrackingList {
-    fn on(&mut self, _: Event<ApiEvent>) -> Option<Message> {
-        None
+    fn on(&mut self, event: Event<ApiEvent>) -> Option<Message> {
+        let direction = match event {
+            Event::Keyboard(KeyEvent {
+                code: Key::Up,
+                modifiers: KeyModifiers::NONE,
+            }) => CmdDirection::Up,
+            Event::Keyboard(KeyEvent {
+                code: Key::Down,
+                modifiers: KeyModifiers::NONE,
+            }) => CmdDirection::Down,
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter,
+                modifiers: KeyModifiers::NONE,
+            }) => {
+                return match self.state() {
+                    State::One(StateValue::Usize(row)) if row >= HEADER_ROWS => Some(
+                        Message::TrackSubmitted((row - HEADER_ROWS) / ROWS_PER_TRACK),
+                    ),
+                    _ => None,
+                };
+            }
+            _ => return None,
+        };
+
+        // Every track is followed by a spacer row, which is skipped over
+        for _ in 0..ROWS_PER_TRACK {
+            self.perform(Cmd::Move(direction));
+        }
+        match self.state() {
+            State::One(StateValue::Usize(row)) if row >= HEADER_ROWS => {
+                Some(Message::TrackChanged((row - HEADER_ROWS) / ROWS_PER_TRACK))
+            }
+            _ => Some(Message::TrackChanged(0)),
+        }
     }
 }
@@ -39,6 +39,7 @@ fn main() -> Result<()> {
             model.redraw = false;
         }
     }
+    model.stop_bookings();
     let _ = model.terminal.leave_alternate_screen();
     let _ = model.terminal.disable_raw_mode();
     let _ = model.terminal.clear_screen();
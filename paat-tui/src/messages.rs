@@ -1,3 +1,4 @@
+use crate::{components::BookingState, ports::BookingPreselection};
 use paat_core::types::event::{EventMap, WaitForSpot};
 
 #[derive(Debug, PartialEq, Eq)]
@@ -7,6 +8,7 @@ pub enum Message {
     DepartureDateSubmitted(String),
     EventsReceived(EventMap),
     WaitResultReceived((String, WaitForSpot)),
+    WaitErrorReceived((String, String)),
     FerryChanged(usize),
     FerrySubmitted,
     LineChanged(usize),
@@ -17,4 +19,14 @@ pub enum Message {
     ClearAll,
     BackToCalendar,
     KillTheAlarm,
+    AttachBooking,
+    PreselectBooking,
+    TrackChanged(usize),
+    TrackSubmitted(usize),
+    BookingIdChanged,
+    BookingIdSubmitted(String),
+    BookingUpdateReceived((String, BookingState)),
+    BookingErrorReceived((String, String)),
+    BookingFoundReceived(BookingPreselection),
+    BookingLookupErrorReceived(String),
 }
@@ -1,30 +1,40 @@
 use crate::{
     components::{
-        AppHeader, ComponentId, DepartureDate, HeaderAttributes, HiddenHandler, SelectFerry,
-        SelectLine, TrackingList, TrackingListElement,
+        AppHeader, BookingId, BookingState, ComponentId, DepartureDate, HeaderAttributes,
+        HiddenHandler, SelectFerry, SelectLine, TrackingList, TrackingListElement,
     },
     localization::fl,
     messages::Message,
-    ports::{ApiClient, ApiEvent},
+    ports::{push_api_event, ApiClient, ApiEvent, ApiEventQueue, BookingPreselection},
     style::{CALENDAR_WIDTH, DATE_SELECT_WIDTH, LINE_SELECT_WIDTH},
 };
 use anyhow::Result;
-use chrono::NaiveDate;
+use chrono::{Local, NaiveDate};
+use log::debug;
 use paat_core::{
+    analytics::{AdaptiveSchedule, SpotStatistics},
+    booking::{
+        change_booking, flow::BookingProgress, options::BookingOptions, selectors::SelectorProfile,
+    },
+    booking_client::BookingClient,
     client::Client,
+    config::Config,
     constants::TIMEOUT_BETWEEN_REQUESTS,
-    datetime::get_naive_date_from_output_format,
-    sound::play_infinite_sound,
+    datetime::{get_naive_date_from_output_format, naive_date_to_output_string},
+    mqtt::MqttPublisher,
+    notification::{Dispatcher, Notification, NotificationKind},
+    recorder::Recorder,
     types::{
-        event::{EventMap, WaitForSpot},
+        event::{Event as PaatEvent, EventMap, WaitForSpot},
         Direction as PaatDirection,
     },
 };
-use std::{collections::BTreeMap, time::Duration};
-use tokio::{
-    runtime::Runtime,
-    sync::oneshot::{self, Sender},
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::Duration,
 };
+use tokio::{runtime::Runtime, task::JoinHandle};
 use tuirealm::{
     props::{PropPayload, PropValue},
     terminal::TerminalBridge,
@@ -39,17 +49,34 @@ pub struct AppState {
     events: EventMap,
     api_clients: Vec<ApiClient>,
     track_list: Vec<TrackingListElement>,
+    booking_track: Option<String>,
+    preselect_booking: bool,
+    pending_booking: Option<BookingPreselection>,
+    error_log: VecDeque<String>,
+}
+
+pub struct BookingSetup {
+    selectors: SelectorProfile,
+    options: BookingOptions,
+    auto_change: bool,
 }
 
+const ERROR_LOG_LENGTH: usize = 8;
+const ERROR_LOG_TIME_FORMAT: &str = "%H:%M:%S";
+
 pub struct Model {
     pub app: Application<ComponentId, Message, ApiEvent>,
     pub quit: bool,
     pub redraw: bool,
     pub terminal: TerminalBridge,
     pub state: AppState,
-    pub alarm: Option<Sender<()>>,
+    pub dispatcher: Arc<Dispatcher>,
     pub client: Client,
     pub runtime: Runtime,
+    pub api_events: ApiEventQueue,
+    pub booking: Arc<BookingSetup>,
+    pub booking_tasks: Vec<JoinHandle<()>>,
+    pub continuous: bool,
 }
 
 impl Default for Model {
@@ -57,20 +84,51 @@ impl Default for Model {
         let timeout_between_requests = std::env::var("TIMEOUT_BETWEEN_REQUESTS")
             .map(|timeout| timeout.parse::<u64>().unwrap_or(TIMEOUT_BETWEEN_REQUESTS))
             .unwrap_or(TIMEOUT_BETWEEN_REQUESTS);
-        let client = Client::new(Duration::from_secs(timeout_between_requests));
+        let config = Config::load().expect("Cannot load configuration");
+        let dispatcher = Arc::new(Dispatcher::from_config_without_terminal(&config.notifiers));
+        let booking = Arc::new(BookingSetup {
+            selectors: config
+                .booking
+                .selector_profile()
+                .expect("Cannot load booking selectors"),
+            options: config.booking.options.clone(),
+            auto_change: config.booking.auto_change,
+        });
         let runtime = tokio::runtime::Builder::new_multi_thread()
             .enable_all()
             .build()
             .unwrap();
+        let mut client = Client::new(Duration::from_secs(timeout_between_requests))
+            .with_stop_before_departure(config.stop_before_departure());
+        if let Some(mqtt_config) = config.mqtt {
+            let _runtime_guard = runtime.enter();
+            let publisher = MqttPublisher::new(mqtt_config).expect("Cannot start MQTT publisher");
+            client = client.with_observer(Arc::new(publisher));
+        }
+        if let Some(recorder_config) = config.recorder {
+            let recorder =
+                Recorder::new(recorder_config.clone()).expect("Cannot open capacity history");
+            if let Some(schedule_config) = config.schedule {
+                let statistics = SpotStatistics::load(&recorder_config.path)
+                    .expect("Cannot load capacity history");
+                client = client
+                    .with_schedule(Arc::new(AdaptiveSchedule::new(statistics, schedule_config)));
+            }
+            client = client.with_observer(Arc::new(recorder));
+        }
         Self {
             app: Self::init_app(),
             quit: false,
             redraw: true,
             terminal: TerminalBridge::new().expect("Cannot initialize terminal"),
             state: AppState::default(),
-            alarm: None,
+            dispatcher,
             client,
             runtime,
+            api_events: Arc::new(Mutex::new(Vec::new())),
+            booking,
+            booking_tasks: Vec::new(),
+            continuous: config.continuous,
         }
     }
 }
@@ -112,12 +170,18 @@ impl Model {
                         .as_ref(),
                     )
                     .split(vertical_fixer[0]);
+                let tracking_column = Layout::default()
+                    .direction(Direction::Vertical)
+                    .margin(0)
+                    .constraints([Constraint::Length(3), Constraint::Min(0)].as_ref())
+                    .split(bottom_row[3]);
                 app.view(&ComponentId::Header, f, chunks[0]);
                 app.view(&ComponentId::HiddenHandler, f, chunks[1]);
                 app.view(&ComponentId::DepartureDate, f, bottom_row[0]);
                 app.view(&ComponentId::SelectLine, f, bottom_row[1]);
                 app.view(&ComponentId::SelectFerry, f, bottom_row[2]);
-                app.view(&ComponentId::TrackingList, f, bottom_row[3])
+                app.view(&ComponentId::BookingId, f, tracking_column[0]);
+                app.view(&ComponentId::TrackingList, f, tracking_column[1])
             })
             .is_ok());
     }
@@ -125,8 +189,13 @@ impl Model {
     fn configure_listener(&mut self) -> Result<()> {
         if let Some(departure_date) = self.state.departure_date {
             if let Some(direction) = self.state.direction {
-                let api_client =
-                    ApiClient::try_new(&self.client, &self.runtime, departure_date, direction)?;
+                let api_client = ApiClient::try_new(
+                    &self.client,
+                    &self.runtime,
+                    departure_date,
+                    direction,
+                    self.api_events.clone(),
+                )?;
                 self.state.api_clients.push(api_client.clone());
                 assert!(self
                     .app
@@ -143,17 +212,186 @@ impl Model {
         Ok(())
     }
 
-    fn play_music(&mut self) {
-        if self.alarm.is_none() {
-            let (sender, receiver) = oneshot::channel::<()>();
-            self.runtime.spawn(async move {
-                play_infinite_sound(receiver).await?;
-                anyhow::Result::<()>::Ok(())
-            });
-            self.alarm = Some(sender);
+    fn notify(&self, notification: Notification) {
+        let dispatcher = self.dispatcher.clone();
+        self.runtime.spawn(async move {
+            if let Err(notification_error) = dispatcher.dispatch(&notification).await {
+                debug!("{}", notification_error);
+            }
+        });
+    }
+
+    fn look_up_booking(&self, event_uuid: String, booking_id: String) {
+        let api_events = self.api_events.clone();
+        self.runtime.spawn(async move {
+            let api_event = match BookingClient::new().lookup_booking(&booking_id).await {
+                Ok(booking_details) => ApiEvent::BookingUpdate((
+                    event_uuid,
+                    BookingState::Attached(booking_details.to_string()),
+                )),
+                Err(lookup_error) => ApiEvent::BookingError((event_uuid, lookup_error.to_string())),
+            };
+            push_api_event(&api_events, api_event);
+        });
+    }
+
+    fn look_up_preselection(&self, booking_id: String) {
+        let api_events = self.api_events.clone();
+        self.runtime.spawn(async move {
+            let api_event = match BookingClient::new().lookup_booking(&booking_id).await {
+                Ok(booking_details) => ApiEvent::BookingFound(BookingPreselection {
+                    line_index: booking_details
+                        .direction
+                        .get_line_index()
+                        .unwrap_or_default(),
+                    date: booking_details.date,
+                    description: booking_details.to_string(),
+                    booking_id,
+                }),
+                Err(lookup_error) => {
+                    ApiEvent::BookingLookupError(format!("{}: {}", booking_id, lookup_error))
+                }
+            };
+            push_api_event(&api_events, api_event);
+        });
+    }
+
+    fn change_booking(&mut self, element: TrackingListElement, event: PaatEvent) {
+        let booking_id = match element.booking_id.clone() {
+            Some(booking_id) => booking_id,
+            None => return,
+        };
+        let api_events = self.api_events.clone();
+        let booking = self.booking.clone();
+        self.booking_tasks.retain(|task| !task.is_finished());
+        let task = self.runtime.spawn(async move {
+            let progress_events = api_events.clone();
+            let progress_uuid = element.event_uuid.clone();
+            let on_progress = move |progress: &BookingProgress| {
+                push_api_event(
+                    &progress_events,
+                    ApiEvent::BookingUpdate((
+                        progress_uuid.clone(),
+                        BookingState::Running(progress.to_string()),
+                    )),
+                );
+            };
+            let booking_state = match change_booking(
+                &booking_id,
+                &event,
+                &element.direction(),
+                &element.date(),
+                &booking.selectors,
+                &booking.options,
+                &on_progress,
+            )
+            .await
+            {
+                Ok(outcome) if outcome.is_changed() => BookingState::Changed(outcome.to_string()),
+                Ok(outcome) => BookingState::Failed(outcome.to_string()),
+                Err(booking_error) => BookingState::Failed(booking_error.to_string()),
+            };
+            push_api_event(
+                &api_events,
+                ApiEvent::BookingUpdate((element.event_uuid, booking_state)),
+            );
+        });
+        self.booking_tasks.push(task);
+    }
+
+    pub fn stop_bookings(&mut self) {
+        // Running changes close their browsers once they see the cancellation
+        self.booking.options.cancel.cancel();
+        for task in self.booking_tasks.drain(..) {
+            if let Err(err) = self.runtime.block_on(task) {
+                debug!("{}", err);
+            }
         }
     }
 
+    fn ask_booking_id(&mut self, element: &TrackingListElement) {
+        self.state.booking_track = Some(element.event_uuid.clone());
+        assert!(self
+            .app
+            .attr(
+                &ComponentId::BookingId,
+                Attribute::Title,
+                AttrValue::Title((
+                    format!("{} {}", fl!("booking-id"), element.description()),
+                    Alignment::Left
+                ))
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .attr(
+                &ComponentId::BookingId,
+                Attribute::Value,
+                AttrValue::String(element.booking_id.clone().unwrap_or_default())
+            )
+            .is_ok());
+        assert!(self.app.active(&ComponentId::BookingId).is_ok());
+    }
+
+    fn reset_booking_input(&mut self) {
+        assert!(self
+            .app
+            .attr(
+                &ComponentId::BookingId,
+                Attribute::Value,
+                AttrValue::String(String::new())
+            )
+            .is_ok());
+        assert!(self
+            .app
+            .attr(
+                &ComponentId::BookingId,
+                Attribute::Title,
+                AttrValue::Title((fl!("booking-id"), Alignment::Left))
+            )
+            .is_ok());
+    }
+
+    fn select_track(&mut self, row: usize) {
+        assert!(self
+            .app
+            .attr(
+                &ComponentId::TrackingList,
+                Attribute::Value,
+                AttrValue::Payload(PropPayload::One(PropValue::Usize(row)))
+            )
+            .is_ok());
+    }
+
+    fn refresh_tracking_list(&mut self) {
+        let (attribute, value) = TrackingList::build_table_rows(self.state.track_list.clone());
+        assert!(self
+            .app
+            .attr(&ComponentId::TrackingList, attribute, value)
+            .is_ok());
+    }
+
+    fn log_error(&mut self, error: String) {
+        let error = format!("{} {}", Local::now().format(ERROR_LOG_TIME_FORMAT), error);
+        self.state.error_log.push_front(error);
+        self.state.error_log.truncate(ERROR_LOG_LENGTH);
+        let error_text = self
+            .state
+            .error_log
+            .iter()
+            .cloned()
+            .collect::<Vec<String>>()
+            .join("\n");
+        assert!(self
+            .app
+            .attr(
+                &ComponentId::Header,
+                Attribute::Custom(HeaderAttributes::ERROR_TEXT),
+                AttrValue::String(error_text)
+            )
+            .is_ok());
+    }
+
     fn reset_selection(&mut self) {
         self.state.direction = None;
         self.state.departure_date = None;
@@ -224,7 +462,14 @@ impl Model {
             .mount(
                 ComponentId::HiddenHandler,
                 Box::new(HiddenHandler::default()),
-                vec![Sub::new(SubEventClause::Any, SubClause::Always)]
+                vec![Sub::new(
+                    SubEventClause::Any,
+                    SubClause::not(SubClause::HasAttrValue(
+                        ComponentId::BookingId,
+                        Attribute::Focus,
+                        AttrValue::Flag(true)
+                    ))
+                )]
             )
             .is_ok());
         assert!(app
@@ -234,6 +479,13 @@ impl Model {
                 vec![]
             )
             .is_ok());
+        assert!(app
+            .mount(
+                ComponentId::BookingId,
+                Box::new(BookingId::default()),
+                vec![]
+            )
+            .is_ok());
         assert!(app.active(&ComponentId::DepartureDate).is_ok());
         app
     }
@@ -298,14 +550,7 @@ impl Update<Message> for Model {
                             assert!(self.app.active(&ComponentId::SelectFerry).is_ok());
                         }
                         Err(_) => {
-                            assert!(self
-                                .app
-                                .attr(
-                                    &ComponentId::Header,
-                                    Attribute::Custom(HeaderAttributes::ERROR_TEXT),
-                                    AttrValue::String(fl!("event-fetch-error"))
-                                )
-                                .is_ok());
+                            self.log_error(fl!("event-fetch-error"));
                         }
                     }
                     None
@@ -344,39 +589,118 @@ impl Update<Message> for Model {
                         .collect::<Vec<&paat_core::types::event::Event>>();
                     events.sort_by_key(|event| event.start.clone());
                     let event = events[line_index];
+                    let event_uuid = event.uuid.clone();
                     self.state.api_clients.last().unwrap().start_monitoring(
                         &self.client,
                         &self.runtime,
                         event.uuid.clone(),
+                        self.continuous,
                     );
-                    if !self.get_event_ids().contains(&event.uuid) {
-                        self.state.track_list.push(TrackingListElement::new(
+                    let new_element = if !self.get_event_ids().contains(&event.uuid) {
+                        let element = TrackingListElement::new(
                             self.state.direction,
                             self.state.departure_date,
                             event,
-                        ));
-                        let (attribute, value) =
-                            TrackingList::build_table_rows(self.state.track_list.clone());
-                        assert!(self
-                            .app
-                            .attr(&ComponentId::TrackingList, attribute, value)
-                            .is_ok());
-                    }
+                        );
+                        self.state.track_list.push(element.clone());
+                        self.refresh_tracking_list();
+                        Some(element)
+                    } else {
+                        None
+                    };
                     self.reset_selection();
+                    if let Some(preselection) = self.state.pending_booking.take() {
+                        for element in self.state.track_list.iter_mut() {
+                            if element.event_uuid == event_uuid {
+                                element.booking_id = Some(preselection.booking_id.clone());
+                                element.booking =
+                                    Some(BookingState::Attached(preselection.description.clone()));
+                            }
+                        }
+                        self.reset_booking_input();
+                        self.refresh_tracking_list();
+                    } else if let Some(element) = new_element {
+                        self.ask_booking_id(&element);
+                    }
                     None
                 }
                 Message::WaitResultReceived((event_uuid, spot)) => {
-                    let mut spot_found = false;
-                    if let WaitForSpot::Done(event) = spot {
-                        for element in self.state.track_list.iter_mut() {
-                            if element.event_uuid == event_uuid {
-                                element.free_spots = Some(event.capacities.small_vehicles as usize);
-                                spot_found = true;
+                    let mut notifications = Vec::new();
+                    let mut booking_changes = Vec::new();
+                    for element in self.state.track_list.iter_mut() {
+                        if element.event_uuid == event_uuid {
+                            match &spot {
+                                WaitForSpot::Done(event) => {
+                                    element.status.record_success();
+                                    let is_found = element.status.free_spots.is_none();
+                                    if is_found {
+                                        notifications.push(element.notification(
+                                            NotificationKind::SpotFound,
+                                            event.to_owned(),
+                                        ));
+                                    }
+                                    element.status.free_spots =
+                                        Some(event.capacities.small_vehicles as usize);
+                                    if is_found
+                                        && self.booking.auto_change
+                                        && element.can_change_booking()
+                                    {
+                                        element.booking =
+                                            Some(BookingState::Running(fl!("booking-starting")));
+                                        booking_changes.push((element.clone(), event.to_owned()));
+                                    }
+                                }
+                                WaitForSpot::Lost(event) => {
+                                    element.status.record_success();
+                                    element.status.free_spots = None;
+                                    notifications.push(element.notification(
+                                        NotificationKind::SpotLost,
+                                        event.to_owned(),
+                                    ));
+                                }
+                                WaitForSpot::Regained(event) => {
+                                    element.status.record_success();
+                                    element.status.free_spots =
+                                        Some(event.capacities.small_vehicles as usize);
+                                    notifications.push(element.notification(
+                                        NotificationKind::SpotRegained,
+                                        event.to_owned(),
+                                    ));
+                                    if self.booking.auto_change && element.can_change_booking() {
+                                        element.booking =
+                                            Some(BookingState::Running(fl!("booking-starting")));
+                                        booking_changes.push((element.clone(), event.to_owned()));
+                                    }
+                                }
+                                WaitForSpot::Expired => element.status.expired = true,
+                                WaitForSpot::Waiting => element.status.record_success(),
                             }
                         }
                     }
-                    if spot_found {
-                        self.play_music();
+                    for notification in notifications {
+                        self.notify(notification);
+                    }
+                    for (element, event) in booking_changes {
+                        self.change_booking(element, event);
+                    }
+                    let (attribute, value) =
+                        TrackingList::build_table_rows(self.state.track_list.clone());
+                    assert!(self
+                        .app
+                        .attr(&ComponentId::TrackingList, attribute, value)
+                        .is_ok());
+                    None
+                }
+                Message::WaitErrorReceived((event_uuid, error)) => {
+                    let mut failed_tracks = Vec::new();
+                    for element in self.state.track_list.iter_mut() {
+                        if element.event_uuid == event_uuid {
+                            element.status.record_failure(error.clone());
+                            failed_tracks.push(element.description());
+                        }
+                    }
+                    for failed_track in failed_tracks {
+                        self.log_error(format!("{}: {}", failed_track, error));
                     }
                     let (attribute, value) =
                         TrackingList::build_table_rows(self.state.track_list.clone());
@@ -388,7 +712,9 @@ impl Update<Message> for Model {
                 }
                 Message::TickFromListener => {
                     for element in self.state.track_list.iter_mut() {
-                        element.counter = (element.counter % usize::MAX) + 1;
+                        if !element.status.is_stalled() && !element.status.expired {
+                            element.counter = (element.counter % usize::MAX) + 1;
+                        }
                     }
                     let (attribute, value) =
                         TrackingList::build_table_rows(self.state.track_list.clone());
@@ -399,6 +725,10 @@ impl Update<Message> for Model {
                     None
                 }
                 Message::BackToCalendar => {
+                    if self.state.pending_booking.take().is_some() {
+                        self.reset_booking_input();
+                    }
+                    self.select_track(TrackingList::hidden_row());
                     self.reset_selection();
                     assert!(self.app.active(&ComponentId::DepartureDate).is_ok());
                     None
@@ -416,7 +746,7 @@ impl Update<Message> for Model {
                 Message::ClearFinished => {
                     self.state
                         .track_list
-                        .retain(|element| element.free_spots.is_some());
+                        .retain(|element| element.status.free_spots.is_some());
                     let (attribute, value) =
                         TrackingList::build_table_rows(self.state.track_list.clone());
                     assert!(self
@@ -428,7 +758,7 @@ impl Update<Message> for Model {
                 Message::ClearUnfinished => {
                     self.state
                         .track_list
-                        .retain(|element| element.free_spots.is_none());
+                        .retain(|element| element.status.free_spots.is_none());
                     let (attribute, value) =
                         TrackingList::build_table_rows(self.state.track_list.clone());
                     assert!(self
@@ -438,11 +768,129 @@ impl Update<Message> for Model {
                     None
                 }
                 Message::KillTheAlarm => {
-                    let alarm = self.alarm.take();
-                    if let Some(alarm) = alarm {
-                        alarm.send(()).unwrap();
+                    self.dispatcher.dismiss();
+                    None
+                }
+                Message::AttachBooking => {
+                    if !self.state.track_list.is_empty() {
+                        self.select_track(TrackingList::track_row(0));
+                        assert!(self.app.active(&ComponentId::TrackingList).is_ok());
+                    }
+                    None
+                }
+                Message::PreselectBooking => {
+                    self.state.booking_track = None;
+                    self.state.pending_booking = None;
+                    self.state.preselect_booking = true;
+                    self.select_track(TrackingList::hidden_row());
+                    self.reset_booking_input();
+                    assert!(self.app.active(&ComponentId::BookingId).is_ok());
+                    None
+                }
+                Message::TrackChanged(track_index) => {
+                    self.select_track(TrackingList::track_row(track_index));
+                    None
+                }
+                Message::TrackSubmitted(track_index) => {
+                    if let Some(element) = self.state.track_list.get(track_index).cloned() {
+                        self.ask_booking_id(&element);
                     }
-                    self.alarm = None;
+                    None
+                }
+                Message::BookingIdChanged => None,
+                Message::BookingIdSubmitted(booking_id) => {
+                    let booking_id = booking_id.trim().to_string();
+                    let preselect_booking = std::mem::take(&mut self.state.preselect_booking);
+                    if let Some(event_uuid) = self.state.booking_track.take() {
+                        if !booking_id.is_empty() {
+                            for element in self.state.track_list.iter_mut() {
+                                if element.event_uuid == event_uuid {
+                                    element.booking_id = Some(booking_id.clone());
+                                    element.booking = None;
+                                }
+                            }
+                            self.look_up_booking(event_uuid, booking_id);
+                        }
+                    } else if preselect_booking && !booking_id.is_empty() {
+                        self.look_up_preselection(booking_id);
+                    }
+                    self.reset_booking_input();
+                    self.select_track(TrackingList::hidden_row());
+                    self.refresh_tracking_list();
+                    self.reset_selection();
+                    None
+                }
+                Message::BookingUpdateReceived((event_uuid, booking_state)) => {
+                    for element in self.state.track_list.iter_mut() {
+                        // A late lookup must not hide a change that has already started
+                        let is_late_lookup = matches!(booking_state, BookingState::Attached(_))
+                            && !element.can_change_booking();
+                        if element.event_uuid == event_uuid && !is_late_lookup {
+                            element.booking = Some(booking_state.clone());
+                        }
+                    }
+                    self.refresh_tracking_list();
+                    None
+                }
+                Message::BookingErrorReceived((event_uuid, error)) => {
+                    let failed_tracks = self
+                        .state
+                        .track_list
+                        .iter()
+                        .filter(|element| element.event_uuid == event_uuid)
+                        .map(|element| element.description())
+                        .collect::<Vec<String>>();
+                    for failed_track in failed_tracks {
+                        self.log_error(format!("{}: {}", failed_track, error));
+                    }
+                    None
+                }
+                Message::BookingFoundReceived(preselection) => {
+                    assert!(self
+                        .app
+                        .attr(
+                            &ComponentId::DepartureDate,
+                            Attribute::Value,
+                            AttrValue::String(naive_date_to_output_string(&preselection.date))
+                        )
+                        .is_ok());
+                    assert!(self
+                        .app
+                        .attr(
+                            &ComponentId::SelectLine,
+                            Attribute::Value,
+                            AttrValue::Payload(PropPayload::One(PropValue::Usize(
+                                preselection.line_index
+                            )))
+                        )
+                        .is_ok());
+                    // The booking is shown in the input until a sailing is chosen for it
+                    assert!(self
+                        .app
+                        .attr(
+                            &ComponentId::BookingId,
+                            Attribute::Title,
+                            AttrValue::Title((
+                                format!("{} {}", fl!("booking-id"), preselection.description),
+                                Alignment::Left
+                            ))
+                        )
+                        .is_ok());
+                    assert!(self
+                        .app
+                        .attr(
+                            &ComponentId::BookingId,
+                            Attribute::Value,
+                            AttrValue::String(preselection.booking_id.clone())
+                        )
+                        .is_ok());
+                    self.state.pending_booking = Some(preselection);
+                    self.select_track(TrackingList::hidden_row());
+                    assert!(self.app.active(&ComponentId::DepartureDate).is_ok());
+                    None
+                }
+                Message::BookingLookupErrorReceived(lookup_error) => {
+                    self.log_error(lookup_error);
                     None
                 }
             }
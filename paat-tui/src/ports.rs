@@ -1,8 +1,9 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
+use crate::components::BookingState;
 use anyhow::Result;
 use chrono::NaiveDate;
-use futures::{lock::Mutex, StreamExt};
+use futures::StreamExt;
 use paat_core::{
     client::Client,
     types::{
@@ -20,16 +21,38 @@ use tuirealm::{
 pub enum ApiEvent {
     FetchedEvents(EventMap),
     WaitResult((String, WaitForSpot)),
+    WaitError((String, String)),
+    BookingUpdate((String, BookingState)),
+    BookingError((String, String)),
+    BookingFound(BookingPreselection),
+    BookingLookupError(String),
     NoOperation,
 }
 
+/// A looked up booking whose line and date seed the pickers of a new track
+#[derive(PartialEq, Clone, PartialOrd, Eq, Debug)]
+pub struct BookingPreselection {
+    pub booking_id: String,
+    pub line_index: usize,
+    pub date: NaiveDate,
+    pub description: String,
+}
+
+pub type ApiEventQueue = Arc<Mutex<Vec<ApiEvent>>>;
+
+pub fn push_api_event(api_events: &ApiEventQueue, api_event: ApiEvent) {
+    if let Ok(mut api_events) = api_events.lock() {
+        api_events.push(api_event);
+    }
+}
+
 #[derive(Clone)]
 pub struct ApiClient {
     departure_date: NaiveDate,
     direction: Direction,
     event_map: EventMap,
     event_list_sent: bool,
-    wait_list: Arc<Mutex<Vec<(String, WaitForSpot)>>>,
+    wait_list: ApiEventQueue,
 }
 
 impl ApiClient {
@@ -38,6 +61,7 @@ impl ApiClient {
         runtime: &Runtime,
         departure_date: NaiveDate,
         direction: Direction,
+        wait_list: ApiEventQueue,
     ) -> Result<Self> {
         let event_map = runtime.block_on(event_client.fetch_events(&departure_date, &direction))?;
 
@@ -46,22 +70,38 @@ impl ApiClient {
             direction,
             event_map,
             event_list_sent: false,
-            wait_list: Arc::new(Mutex::new(Vec::new())),
+            wait_list,
         })
     }
 
-    pub fn start_monitoring(&self, event_client: &Client, runtime: &Runtime, event_uuid: String) {
+    pub fn start_monitoring(
+        &self,
+        event_client: &Client,
+        runtime: &Runtime,
+        event_uuid: String,
+        continuous: bool,
+    ) {
         let departure = self.departure_date.clone();
         let direction = self.direction.clone();
         let event_uuid = event_uuid.to_owned();
         let event_client = event_client.to_owned();
         let wait_list = self.wait_list.clone();
         runtime.spawn(async move {
-            let mut stream =
-                Box::pin(event_client.create_wait_stream(&departure, &direction, &event_uuid));
-            while let Some(Ok(wait_result)) = stream.next().await {
-                if let Some(mut wait_list) = wait_list.try_lock() {
-                    wait_list.push((event_uuid.clone(), wait_result))
+            let mut stream = Box::pin(event_client.create_spot_stream(
+                &departure,
+                &direction,
+                &event_uuid,
+                continuous,
+            ));
+            while let Some(wait_result) = stream.next().await {
+                let found = matches!(wait_result, Ok(WaitForSpot::Done(_)));
+                let api_event = match wait_result {
+                    Ok(wait_result) => ApiEvent::WaitResult((event_uuid.clone(), wait_result)),
+                    Err(error) => ApiEvent::WaitError((event_uuid.clone(), error.to_string())),
+                };
+                push_api_event(&wait_list, api_event);
+                if found && !continuous {
+                    break;
                 }
             }
         });
@@ -76,9 +116,9 @@ impl Poll<ApiEvent> for ApiClient {
                 self.event_map.clone(),
             ))));
         }
-        if let Some(mut wait_list) = self.wait_list.clone().try_lock() {
-            if let Some(wait_result) = wait_list.pop() {
-                return Ok(Some(Event::User(ApiEvent::WaitResult(wait_result))));
+        if let Ok(mut wait_list) = self.wait_list.try_lock() {
+            if !wait_list.is_empty() {
+                return Ok(Some(Event::User(wait_list.remove(0))));
             }
         }
 